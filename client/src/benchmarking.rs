@@ -50,6 +50,52 @@ impl frame_benchmarking_cli::ExtrinsicBuilder for RemarkBuilder {
 	}
 }
 
+/// Generates `Balances::TransferKeepAlive` extrinsics for the benchmarks.
+///
+/// Note: Should only be used for benchmarking.
+pub struct TransferKeepAliveBuilder {
+	client: Arc<Client>,
+	dest: AccountId,
+	value: Balance,
+}
+
+impl TransferKeepAliveBuilder {
+	/// Creates a new [`Self`] from the given client.
+	pub fn new(client: Arc<Client>, dest: AccountId, value: Balance) -> Self {
+		Self { client, dest, value }
+	}
+}
+
+impl frame_benchmarking_cli::ExtrinsicBuilder for TransferKeepAliveBuilder {
+	fn pallet(&self) -> &str {
+		"balances"
+	}
+
+	fn extrinsic(&self) -> &str {
+		"transfer_keep_alive"
+	}
+
+	fn build(&self, nonce: u32) -> std::result::Result<OpaqueExtrinsic, &'static str> {
+		with_client! {
+			self.client.as_ref(), client, {
+				use runtime::{BalancesCall, RuntimeCall};
+
+				let call = RuntimeCall::Balances(BalancesCall::transfer_keep_alive {
+					dest: self.dest.clone().into(),
+					value: self.value,
+				});
+				let signer = Sr25519Keyring::Bob.pair();
+
+				let period = ternoa_runtime_common::BlockHashCount::get().checked_next_power_of_two().map(|c| c / 2).unwrap_or(2) as u64;
+				let genesis = client.usage_info().chain.best_hash;
+				let best_block = client.chain_info().best_number;
+
+				Ok(client.sign_call(call, nonce, period, genesis, signer, best_block))
+			}
+		}
+	}
+}
+
 /// Helper trait to implement [`frame_benchmarking_cli::ExtrinsicBuilder`].
 ///
 /// Should only be used for benchmarking since it makes strong assumptions
@@ -89,6 +89,22 @@ pub trait RuntimeApiCollection:
 	+ sp_offchain::OffchainWorkerApi<Block>
 	+ sp_session::SessionKeys<Block>
 	+ sp_authority_discovery::AuthorityDiscoveryApi<Block>
+	+ sp_statement_store::runtime_api::ValidateStatement<Block>
+	+ ternoa_runtime_api::nft::NftApi<Block, AccountId, Balance>
+	+ ternoa_runtime_api::marketplace::MarketplaceApi<Block, AccountId, Balance>
+	+ ternoa_runtime_api::auction::AuctionApi<Block, AccountId, BlockNumber, Balance>
+	+ ternoa_runtime_api::rent::RentApi<Block, AccountId, BlockNumber, Balance>
+	+ ternoa_runtime_api::bridge::BridgeApi<Block, AccountId, BlockNumber, Balance>
+	+ ternoa_runtime_api::dry_run::DryRunApi<Block>
+	+ ternoa_runtime_api::fees::FeesApi<Block, AccountId, Balance>
+	+ ternoa_runtime_api::metrics::MetricsApi<Block>
+	+ ternoa_runtime_api::tee::TeeApi<Block, AccountId, Balance>
+	+ ternoa_runtime_api::staking_rewards::StakingRewardsApi<Block, Balance>
+	+ ternoa_runtime_api::transmission_protocols::TransmissionProtocolsApi<
+		Block,
+		AccountId,
+		BlockNumber,
+	>
 where
 	<Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
 {
@@ -106,7 +122,23 @@ where
 		+ sp_api::Metadata<Block>
 		+ sp_offchain::OffchainWorkerApi<Block>
 		+ sp_session::SessionKeys<Block>
-		+ sp_authority_discovery::AuthorityDiscoveryApi<Block>,
+		+ sp_authority_discovery::AuthorityDiscoveryApi<Block>
+		+ sp_statement_store::runtime_api::ValidateStatement<Block>
+		+ ternoa_runtime_api::nft::NftApi<Block, AccountId, Balance>
+		+ ternoa_runtime_api::marketplace::MarketplaceApi<Block, AccountId, Balance>
+		+ ternoa_runtime_api::auction::AuctionApi<Block, AccountId, BlockNumber, Balance>
+		+ ternoa_runtime_api::rent::RentApi<Block, AccountId, BlockNumber, Balance>
+		+ ternoa_runtime_api::bridge::BridgeApi<Block, AccountId, BlockNumber, Balance>
+		+ ternoa_runtime_api::dry_run::DryRunApi<Block>
+		+ ternoa_runtime_api::fees::FeesApi<Block, AccountId, Balance>
+		+ ternoa_runtime_api::metrics::MetricsApi<Block>
+		+ ternoa_runtime_api::tee::TeeApi<Block, AccountId, Balance>
+		+ ternoa_runtime_api::staking_rewards::StakingRewardsApi<Block, Balance>
+		+ ternoa_runtime_api::transmission_protocols::TransmissionProtocolsApi<
+			Block,
+			AccountId,
+			BlockNumber,
+		>,
 	<Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
 {
 }
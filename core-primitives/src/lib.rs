@@ -37,6 +37,9 @@ pub type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::Account
 /// Balance of an account.
 pub type Balance = u128;
 
+/// Index of a staking era.
+pub type EraIndex = u32;
+
 /// Type used for expressing timestamp.
 pub type Moment = u64;
 
@@ -14,21 +14,28 @@
 // You should have received a copy of the GNU General Public License
 // along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
 
-use super::{get_account_id_from_seed, get_from_seed, AlphanetChainSpec as ChainSpec};
+use super::{
+	genesis_file::{offchain_data, Asset, GenesisFile, TernoaSeed},
+	get_account_id_from_seed, get_from_seed, AlphanetChainSpec as ChainSpec, Extensions,
+};
 use alphanet_runtime::{
-	constants::currency::CAPS, wasm_binary_unwrap, AuthorityDiscoveryConfig, BabeConfig,
-	BalancesConfig, CouncilConfig, GenesisConfig, GrandpaConfig, ImOnlineConfig, SessionConfig,
-	SessionKeys, StakingConfig, SystemConfig, TechnicalMembershipConfig, BABE_GENESIS_EPOCH_CONFIG,
+	constants::currency::CAPS, wasm_binary_unwrap, AssetsConfig, AuthorityDiscoveryConfig,
+	BabeConfig, BalancesConfig, CouncilConfig, GenesisConfig, GrandpaConfig, ImOnlineConfig,
+	Runtime, RuntimeCall, RuntimeOrigin, SessionConfig, SessionKeys, StakingConfig, SystemConfig,
+	TechnicalMembershipConfig, BABE_GENESIS_EPOCH_CONFIG,
 };
+use frame_support::BasicExternalities;
 use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
 use pallet_staking::Forcing;
 use sc_chain_spec::ChainType;
+use sc_service::ChainSpec as _;
 use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
 use sp_consensus_babe::AuthorityId as BabeId;
-use sp_core::sr25519;
 use sp_consensus_grandpa::AuthorityId as GrandpaId;
-use sp_runtime::{BoundedVec, Perbill};
-use ternoa_core_primitives::AccountId;
+use sp_core::sr25519;
+use sp_runtime::{traits::Dispatchable, BoundedVec, BuildStorage, Perbill, Storage};
+use ternoa_core_primitives::{AccountId, Balance};
+use ternoa_runtime_common::technical_collective::TechnicalMaxMembers;
 
 fn session_keys(
 	grandpa: GrandpaId,
@@ -58,10 +65,18 @@ pub fn authority_keys_from_seed(seed: &str) -> AuthorityKeys {
 	)
 }
 
+#[derive(Clone)]
 pub struct GenesisInput {
 	pub initial_authorities: Vec<AuthorityKeys>,
-	pub committee_members: Vec<AccountId>,
+	/// The amount bonded by each authority.
+	pub authority_stake: Balance,
+	pub endowed_accounts: Vec<(AccountId, Balance)>,
+	pub committee_members: BoundedVec<AccountId, TechnicalMaxMembers>,
+	pub council_members: Vec<AccountId>,
 	pub invulnerables: Vec<AccountId>,
+	pub assets: Vec<Asset>,
+	/// The balance of each holder of each asset.
+	pub asset_accounts: Vec<(u32, AccountId, Balance)>,
 }
 
 fn development_accounts() -> Vec<AccountId> {
@@ -83,10 +98,25 @@ fn development_accounts() -> Vec<AccountId> {
 
 fn development_config_genesis() -> GenesisConfig {
 	let initial_authorities = vec![authority_keys_from_seed("Alice")];
-	let committee_members = vec![sr25519_account_from_seed("Alice")];
+	let committee_members = BoundedVec::truncate_from(vec![sr25519_account_from_seed("Alice")]);
 	let invulnerables = vec![initial_authorities[0].0.clone()];
 
-	let input = GenesisInput { initial_authorities, committee_members, invulnerables };
+	const ENDOWMENT: u128 = 1_000_000 * CAPS;
+	const STASH: u128 = 100 * CAPS;
+
+	let input = GenesisInput {
+		initial_authorities,
+		authority_stake: STASH,
+		endowed_accounts: development_accounts()
+			.into_iter()
+			.map(|account| (account, ENDOWMENT))
+			.collect(),
+		committee_members,
+		council_members: vec![],
+		invulnerables,
+		assets: vec![],
+		asset_accounts: vec![],
+	};
 
 	genesis(input)
 }
@@ -113,19 +143,21 @@ pub fn development_config() -> ChainSpec {
 
 /// Helper function to create GenesisConfig for dev testo
 pub fn genesis(input: GenesisInput) -> GenesisConfig {
-	let GenesisInput { initial_authorities, committee_members, invulnerables } = input;
-
-	let endowed_accounts: Vec<AccountId> = development_accounts();
-
-	const ENDOWMENT: u128 = 1_000_000 * CAPS;
-	const STASH: u128 = 100 * CAPS;
+	let GenesisInput {
+		initial_authorities,
+		authority_stake,
+		endowed_accounts,
+		committee_members,
+		council_members,
+		invulnerables,
+		assets,
+		asset_accounts,
+	} = input;
 
 	GenesisConfig {
 		// Core
 		system: SystemConfig { code: wasm_binary_unwrap().to_vec() },
-		balances: BalancesConfig {
-			balances: endowed_accounts.iter().cloned().map(|x| (x, ENDOWMENT)).collect(),
-		},
+		balances: BalancesConfig { balances: endowed_accounts },
 
 		// Consensus
 		session: SessionConfig {
@@ -150,7 +182,12 @@ pub fn genesis(input: GenesisInput) -> GenesisConfig {
 			stakers: initial_authorities
 				.iter()
 				.map(|x| {
-					(x.0.clone(), x.1.clone(), STASH, alphanet_runtime::StakerStatus::Validator)
+					(
+						x.0.clone(),
+						x.1.clone(),
+						authority_stake,
+						alphanet_runtime::StakerStatus::Validator,
+					)
 				})
 				.collect(),
 			invulnerables,
@@ -162,12 +199,109 @@ pub fn genesis(input: GenesisInput) -> GenesisConfig {
 		transaction_payment: Default::default(),
 		technical_committee: Default::default(),
 		technical_membership: TechnicalMembershipConfig {
-			members: BoundedVec::try_from(committee_members).unwrap(),
+			members: committee_members,
 			..Default::default()
 		},
-		council: CouncilConfig { members: vec![], ..Default::default() },
+		council: CouncilConfig { members: council_members, ..Default::default() },
 		democracy: Default::default(),
 		phragmen_election: Default::default(),
-		assets: Default::default(),
+		assets: AssetsConfig {
+			assets: assets
+				.iter()
+				.map(|asset| {
+					(asset.id, asset.owner.clone(), asset.is_sufficient, asset.min_balance.into())
+				})
+				.collect(),
+			metadata: assets
+				.iter()
+				.map(|asset| {
+					let name = asset.name.clone().into_bytes();
+					(asset.id, name, asset.symbol.clone().into_bytes(), asset.decimals)
+				})
+				.collect(),
+			accounts: asset_accounts,
+		},
+	}
+}
+
+/// Builds the human and raw chain specs of the network described by a genesis file. There is no
+/// human chain spec when the file has Ternoa seed data, which only the raw chain spec can hold.
+pub fn chain_specs_from_file(file: GenesisFile) -> Result<(Option<String>, String), String> {
+	let committee_members = file
+		.technical_committee
+		.clone()
+		.try_into()
+		.map_err(|_| "Too many technical committee members".to_string())?;
+	let mut asset_accounts = Vec::new();
+	for asset in &file.assets {
+		let accounts = asset.accounts().into_iter();
+		asset_accounts.extend(accounts.map(|(account, balance)| (asset.id, account, balance)));
+	}
+
+	let input = GenesisInput {
+		initial_authorities: file.authority_keys(),
+		authority_stake: file.authority_stake,
+		endowed_accounts: file.endowed_accounts(),
+		committee_members,
+		council_members: file.council.clone(),
+		invulnerables: file.invulnerables.clone(),
+		assets: file.assets.clone(),
+		asset_accounts,
+	};
+
+	let mut chain_spec = ChainSpec::from_genesis(
+		&file.name,
+		&file.id,
+		file.chain_type.clone(),
+		move || genesis(input.clone()),
+		file.boot_nodes.clone(),
+		None,
+		Some(file.protocol_id.as_deref().unwrap_or("ternoa")),
+		None,
+		Some(file.properties()),
+		Extensions { consensus: file.consensus.clone(), ..Default::default() },
+	);
+	if file.ternoa.is_empty() {
+		return Ok((Some(chain_spec.as_json(false)?), chain_spec.as_json(true)?))
 	}
-}
\ No newline at end of file
+
+	let mut storage = chain_spec.build_storage()?;
+	seed_ternoa(&mut storage, &file.ternoa)?;
+	chain_spec.set_storage(storage);
+
+	Ok((None, chain_spec.as_json(true)?))
+}
+
+/// Dispatches the calls creating the Ternoa seed data on top of the genesis storage.
+fn seed_ternoa(storage: &mut Storage, seed: &TernoaSeed) -> Result<(), String> {
+	let dispatch = |call: ternoa_nft::Call<Runtime>, origin: RuntimeOrigin| {
+		RuntimeCall::NFT(call)
+			.dispatch(origin)
+			.map(|_| ())
+			.map_err(|e| format!("{:?}", e.error))
+	};
+
+	BasicExternalities::execute_with_storage(storage, || {
+		if let Some(fee) = seed.nft_mint_fee {
+			dispatch(ternoa_nft::Call::set_nft_mint_fee { fee }, RuntimeOrigin::root())?;
+		}
+		for collection in &seed.collections {
+			let call = ternoa_nft::Call::create_collection {
+				offchain_data: offchain_data(&collection.offchain_data)?,
+				limit: collection.limit,
+			};
+			dispatch(call, RuntimeOrigin::signed(collection.owner.clone()))?;
+		}
+		for nft in &seed.nfts {
+			let call = ternoa_nft::Call::create_nft {
+				offchain_data: offchain_data(&nft.offchain_data)?,
+				royalty: nft.royalty,
+				collection_id: nft.collection,
+				is_soulbound: nft.is_soulbound,
+			};
+			dispatch(call, RuntimeOrigin::signed(nft.owner.clone()))?;
+		}
+
+		Ok(())
+	})
+}
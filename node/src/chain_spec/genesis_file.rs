@@ -0,0 +1,378 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Declarative description of a network, read by the `genesis` subcommand.
+//!
+//! Amounts are given in planck, the smallest unit of CAPS or of the asset, either as an integer
+//! or as a decimal string for the amounts that do not fit in a TOML integer.
+
+use std::path::Path;
+
+use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
+use sc_chain_spec::{ChainType, Properties};
+use sc_network::config::MultiaddrWithPeerId;
+use serde::{Deserialize, Deserializer};
+use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
+use sp_consensus_babe::AuthorityId as BabeId;
+use sp_consensus_grandpa::AuthorityId as GrandpaId;
+use sp_core::Get;
+use sp_runtime::{BoundedVec, Permill};
+use ternoa_core_primitives::{AccountId, Balance};
+use ternoa_runtime_common::technical_collective::TechnicalMaxMembers;
+
+use super::ConsensusParams;
+
+/// A network to build the chain spec of.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct GenesisFile {
+	pub name: String,
+	/// The id of the chain, which must start with `alphanet` or `mainnet` so that the node knows
+	/// which runtime to run.
+	pub id: String,
+	#[serde(default)]
+	pub chain_type: ChainType,
+	#[serde(default)]
+	pub boot_nodes: Vec<MultiaddrWithPeerId>,
+	pub protocol_id: Option<String>,
+	/// Defaults to the CAPS token symbol and decimals.
+	pub properties: Option<Properties>,
+	#[serde(default)]
+	pub consensus: ConsensusParams,
+	pub authorities: Vec<Authority>,
+	/// The CAPS bonded by each authority.
+	#[serde(deserialize_with = "planck")]
+	pub authority_stake: Balance,
+	/// The free CAPS of each account.
+	#[serde(default)]
+	pub balances: Vec<Endowment>,
+	#[serde(default)]
+	pub technical_committee: Vec<AccountId>,
+	#[serde(default)]
+	pub council: Vec<AccountId>,
+	/// The validators that cannot be slashed.
+	#[serde(default)]
+	pub invulnerables: Vec<AccountId>,
+	#[serde(default)]
+	pub assets: Vec<Asset>,
+	#[serde(default)]
+	pub ternoa: TernoaSeed,
+}
+
+impl GenesisFile {
+	/// Reads a genesis file, as TOML or JSON depending on its extension.
+	pub fn read(path: &Path) -> Result<Self, String> {
+		let content = std::fs::read_to_string(path)
+			.map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+		let file: Self = match path.extension().and_then(|extension| extension.to_str()) {
+			Some("toml") => toml::from_str(&content).map_err(|e| e.to_string())?,
+			Some("json") => serde_json::from_str(&content).map_err(|e| e.to_string())?,
+			_ => return Err("The genesis file must have a `.toml` or `.json` extension".into()),
+		};
+
+		if file.authorities.is_empty() {
+			return Err("The genesis file must have at least one authority".into())
+		}
+		if file.technical_committee.len() > TechnicalMaxMembers::get() as usize {
+			return Err(format!(
+				"The technical committee can have at most {} members",
+				TechnicalMaxMembers::get()
+			))
+		}
+		for authority in &file.authorities {
+			let endowment = file
+				.balances
+				.iter()
+				.filter(|endowment| endowment.account == authority.stash)
+				.fold(0, |total: Balance, endowment| total.saturating_add(endowment.amount));
+			if endowment < file.authority_stake {
+				return Err(format!(
+					"The stash {} must be endowed with at least the authority stake",
+					authority.stash
+				))
+			}
+		}
+		Ok(file)
+	}
+
+	/// Returns the properties of the chain, defaulting to the CAPS token.
+	pub fn properties(&self) -> Properties {
+		self.properties.clone().unwrap_or_else(|| {
+			let mut properties = Properties::new();
+			properties.insert("tokenSymbol".into(), "CAPS".into());
+			properties.insert("tokenDecimals".into(), 18.into());
+			properties
+		})
+	}
+
+	/// Returns the stash, controller and session keys of each authority.
+	pub fn authority_keys(
+		&self,
+	) -> Vec<(AccountId, AccountId, GrandpaId, BabeId, ImOnlineId, AuthorityDiscoveryId)> {
+		self.authorities
+			.iter()
+			.map(|authority| {
+				(
+					authority.stash.clone(),
+					authority.controller.clone().unwrap_or_else(|| authority.stash.clone()),
+					authority.grandpa.clone(),
+					authority.babe.clone(),
+					authority.im_online.clone(),
+					authority.authority_discovery.clone(),
+				)
+			})
+			.collect()
+	}
+
+	/// Returns the free balance of each account.
+	pub fn endowed_accounts(&self) -> Vec<(AccountId, Balance)> {
+		self.balances
+			.iter()
+			.map(|endowment| (endowment.account.clone(), endowment.amount))
+			.collect()
+	}
+}
+
+/// A validator of the network and its session keys.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Authority {
+	pub stash: AccountId,
+	/// Defaults to the stash.
+	pub controller: Option<AccountId>,
+	pub grandpa: GrandpaId,
+	pub babe: BabeId,
+	pub im_online: ImOnlineId,
+	pub authority_discovery: AuthorityDiscoveryId,
+}
+
+/// An amount of planck given to an account.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Endowment {
+	pub account: AccountId,
+	#[serde(deserialize_with = "planck")]
+	pub amount: Balance,
+}
+
+/// An asset of `pallet_assets` and its holders.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Asset {
+	pub id: u32,
+	pub owner: AccountId,
+	#[serde(default)]
+	pub is_sufficient: bool,
+	/// The minimum balance of an account.
+	#[serde(deserialize_with = "planck")]
+	pub min_balance: Balance,
+	pub name: String,
+	pub symbol: String,
+	pub decimals: u8,
+	#[serde(default)]
+	pub balances: Vec<Endowment>,
+}
+
+impl Asset {
+	/// Returns the balance of each holder.
+	pub fn accounts(&self) -> Vec<(AccountId, Balance)> {
+		self.balances
+			.iter()
+			.map(|endowment| (endowment.account.clone(), endowment.amount))
+			.collect()
+	}
+}
+
+/// Initial state of the Ternoa pallets.
+///
+/// These pallets have no genesis config: their calls are dispatched on top of the genesis
+/// storage, so a network with seed data only gets a raw chain spec. Dispatching charges no
+/// transaction fee, but the NFT owners still pay the mint fee.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct TernoaSeed {
+	/// The CAPS charged to mint an NFT. Defaults to the fee of the runtime.
+	#[serde(deserialize_with = "optional_planck")]
+	pub nft_mint_fee: Option<Balance>,
+	/// The collections to create. They get their index in this list as id.
+	pub collections: Vec<SeedCollection>,
+	/// The NFTs to mint. They get their index in this list as id.
+	pub nfts: Vec<SeedNft>,
+}
+
+impl TernoaSeed {
+	/// Returns whether there is nothing to dispatch.
+	pub fn is_empty(&self) -> bool {
+		self.nft_mint_fee.is_none() && self.collections.is_empty() && self.nfts.is_empty()
+	}
+}
+
+/// A collection created at genesis.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SeedCollection {
+	pub owner: AccountId,
+	pub offchain_data: String,
+	pub limit: Option<u32>,
+}
+
+/// An NFT minted at genesis.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SeedNft {
+	pub owner: AccountId,
+	pub offchain_data: String,
+	/// In parts per million.
+	#[serde(default)]
+	pub royalty: Permill,
+	/// The collection to add the NFT to, which must belong to the owner.
+	pub collection: Option<u32>,
+	#[serde(default)]
+	pub is_soulbound: bool,
+}
+
+/// Converts the off-chain data of a seed collection or NFT to the bounded vector of its call.
+pub fn offchain_data<S: Get<u32>>(data: &str) -> Result<BoundedVec<u8, S>, String> {
+	data.as_bytes()
+		.to_vec()
+		.try_into()
+		.map_err(|_| format!("Off-chain data too long: {}", data))
+}
+
+/// An amount written as an integer, or as a string when it does not fit in a TOML integer.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Amount {
+	Integer(u64),
+	String(String),
+}
+
+fn planck<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Balance, D::Error> {
+	match Amount::deserialize(deserializer)? {
+		Amount::Integer(amount) => Ok(amount.into()),
+		Amount::String(amount) => amount
+			.parse()
+			.map_err(|_| serde::de::Error::custom(format!("Invalid amount: {}", amount))),
+	}
+}
+
+fn optional_planck<'de, D: Deserializer<'de>>(
+	deserializer: D,
+) -> Result<Option<Balance>, D::Error> {
+	planck(deserializer).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::chain_spec::{get_account_id_from_seed, get_from_seed};
+	use serde_json::{json, Value};
+	use sp_core::{crypto::Ss58Codec, sr25519};
+
+	const STAKE: &str = "1000000000000000000000";
+
+	fn alice() -> AccountId {
+		get_account_id_from_seed::<sr25519::Public>("Alice")
+	}
+
+	fn genesis_file(balances: Value) -> Value {
+		json!({
+			"name": "Ternoa Test",
+			"id": "alphanet-test",
+			"authorities": [{
+				"stash": alice().to_ss58check(),
+				"grandpa": get_from_seed::<GrandpaId>("Alice").to_ss58check(),
+				"babe": get_from_seed::<BabeId>("Alice").to_ss58check(),
+				"imOnline": get_from_seed::<ImOnlineId>("Alice").to_ss58check(),
+				"authorityDiscovery": get_from_seed::<AuthorityDiscoveryId>("Alice").to_ss58check(),
+			}],
+			"authorityStake": STAKE,
+			"balances": balances,
+		})
+	}
+
+	/// Reads the given content through a temporary genesis file.
+	fn read(content: &Value) -> Result<GenesisFile, String> {
+		let path = std::env::temp_dir().join(format!(
+			"ternoa-genesis-{}-{:?}.json",
+			std::process::id(),
+			std::thread::current().id()
+		));
+		std::fs::write(&path, content.to_string()).unwrap();
+		let file = GenesisFile::read(&path);
+		std::fs::remove_file(&path).unwrap();
+		file
+	}
+
+	#[test]
+	fn reads_amounts_in_planck() {
+		let file = read(&genesis_file(json!([
+			{ "account": alice().to_ss58check(), "amount": STAKE },
+			{ "account": alice().to_ss58check(), "amount": 5 },
+		])))
+		.unwrap();
+
+		assert_eq!(file.authority_stake, 1_000 * 10u128.pow(18));
+		assert_eq!(file.endowed_accounts(), vec![(alice(), 1_000 * 10u128.pow(18)), (alice(), 5)]);
+		assert_eq!(file.authority_keys()[0].1, alice());
+		assert!(file.ternoa.is_empty());
+		assert_eq!(file.properties().get("tokenSymbol"), Some(&json!("CAPS")));
+	}
+
+	#[test]
+	fn rejects_invalid_genesis_files() {
+		let unendowed =
+			read(&genesis_file(json!([{ "account": alice().to_ss58check(), "amount": 5 }])));
+		assert!(unendowed
+			.unwrap_err()
+			.contains("must be endowed with at least the authority stake"));
+
+		let invalid_amount =
+			read(&genesis_file(json!([{ "account": alice().to_ss58check(), "amount": "1.5" }])));
+		assert!(invalid_amount.unwrap_err().contains("Invalid amount: 1.5"));
+
+		let mut without_authorities = genesis_file(json!([]));
+		without_authorities["authorities"] = json!([]);
+		assert_eq!(
+			read(&without_authorities).unwrap_err(),
+			"The genesis file must have at least one authority"
+		);
+	}
+
+	#[cfg(feature = "alphanet-native")]
+	#[test]
+	fn seed_data_only_gets_a_raw_chain_spec() {
+		use crate::chain_spec::alphanet::chain_specs_from_file;
+
+		let mut content = genesis_file(
+			json!([{ "account": alice().to_ss58check(), "amount": "2000000000000000000000" }]),
+		);
+		let (human, raw) = chain_specs_from_file(read(&content).unwrap()).unwrap();
+		assert!(human.is_some());
+		assert!(raw.contains("\"raw\""));
+
+		content["ternoa"] = json!({
+			"nfts": [{ "owner": alice().to_ss58check(), "offchainData": "ipfs://nft" }],
+		});
+		let (human, raw) = chain_specs_from_file(read(&content).unwrap()).unwrap();
+		assert_eq!(human, None);
+		assert!(raw.contains("\"raw\""));
+
+		content["ternoa"]["nfts"][0]["collection"] = json!(0);
+		assert!(chain_specs_from_file(read(&content).unwrap()).is_err());
+	}
+}
@@ -14,14 +14,17 @@
 // You should have received a copy of the GNU General Public License
 // along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::num::NonZeroU32;
+
 use sc_chain_spec::ChainSpecExtension;
 use serde::{Deserialize, Serialize};
 use sp_core::{Pair, Public};
 use sp_runtime::traits::{IdentifyAccount, Verify};
-use ternoa_core_primitives::{AccountId, Block, Signature};
+use ternoa_core_primitives::{AccountId, Block, BlockNumber, Signature};
 
 #[cfg(feature = "alphanet-native")]
 pub mod alphanet;
+pub mod genesis_file;
 #[cfg(feature = "mainnet-native")]
 pub mod mainnet;
 
@@ -42,6 +45,60 @@ pub struct Extensions {
 	///
 	/// This value will be set by the `sync-state rpc` implementation.
 	pub light_sync_state: sc_sync_state_rpc::LightSyncStateExtension,
+	/// BABE and GRANDPA tuning.
+	///
+	/// The defaults are used for the chain specs that don't set it.
+	#[serde(default)]
+	pub consensus: ConsensusParams,
+}
+
+impl Extensions {
+	/// Returns the extensions of the given chain spec, if it uses this type.
+	pub fn try_get(chain_spec: &dyn sc_service::ChainSpec) -> Option<&Self> {
+		sc_chain_spec::get_extension(chain_spec.extensions())
+	}
+}
+
+/// BABE and GRANDPA parameters that can be tuned per network without a custom binary.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ConsensusParams {
+	/// Interval between the GRANDPA gossip rounds, in milliseconds.
+	pub grandpa_gossip_duration_millis: u64,
+	/// Number of blocks after which GRANDPA justifications are generated and stored. GRANDPA
+	/// divides by it, so 0 is rejected.
+	pub grandpa_justification_period: NonZeroU32,
+	/// Voting rules restricting the blocks the GRANDPA voters vote on.
+	///
+	/// When unset, the default rules of `VotingRulesBuilder` are used.
+	pub grandpa_voting_rules: Option<Vec<VotingRule>>,
+	/// Portion of the slot that BABE can spend on proposing a block.
+	pub babe_block_proposal_slot_portion: f32,
+	/// Maximum portion of the slot that BABE can spend on proposing a block when it is lenient
+	/// after missed slots.
+	pub babe_max_block_proposal_slot_portion: Option<f32>,
+}
+
+impl Default for ConsensusParams {
+	fn default() -> Self {
+		Self {
+			grandpa_gossip_duration_millis: 333,
+			grandpa_justification_period: NonZeroU32::new(512).expect("512 is not 0; qed"),
+			grandpa_voting_rules: None,
+			babe_block_proposal_slot_portion: 0.5,
+			babe_max_block_proposal_slot_portion: None,
+		}
+	}
+}
+
+/// A GRANDPA voting rule.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VotingRule {
+	/// Vote on a block at least the given number of blocks behind the best block.
+	BeforeBestBlockBy(BlockNumber),
+	/// Vote on a block at most three quarters of the way into the unfinalized chain.
+	ThreeQuartersOfTheUnfinalizedChain,
 }
 
 // Dummy chain spec, in case when we don't have the native runtime.
@@ -84,4 +141,20 @@ where
 	AccountPublic: From<<TPublic::Pair as Pair>::Public>,
 {
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+	use super::ConsensusParams;
+
+	#[test]
+	fn rejects_zero_justification_period() {
+		let params =
+			serde_json::from_str::<ConsensusParams>(r#"{"grandpaJustificationPeriod": 0}"#);
+		assert!(params.is_err());
+
+		let params =
+			serde_json::from_str::<ConsensusParams>(r#"{"grandpaJustificationPeriod": 16}"#)
+				.unwrap();
+		assert_eq!(params.grandpa_justification_period.get(), 16);
+	}
+}
@@ -27,27 +27,321 @@ pub struct RunCmd {
 	/// telemetry, if telemetry is enabled.
 	#[arg(long)]
 	pub no_hardware_benchmarks: bool,
+
+	/// Seal blocks on demand instead of running BABE and GRANDPA. Development chains only.
+	///
+	/// `instant` seals a block as soon as a transaction enters the pool, `manual` only seals
+	/// blocks requested through `engine_createBlock` and `interval=<ms>` seals a block every
+	/// `<ms>` milliseconds. The sealed blocks are finalized right away, except in `manual` mode
+	/// where `engine_finalizeBlock` has to be called.
+	#[arg(long, value_name = "MODE")]
+	pub sealing: Option<Sealing>,
+}
+
+/// How blocks are sealed when running with `--sealing`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Sealing {
+	/// Seal a block for each transaction entering the pool.
+	Instant,
+	/// Only seal the blocks requested through the `engine_*` RPC methods.
+	Manual,
+	/// Seal a block every given number of milliseconds.
+	Interval(u64),
+}
+
+impl std::str::FromStr for Sealing {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"instant" => Ok(Self::Instant),
+			"manual" => Ok(Self::Manual),
+			_ => s
+				.strip_prefix("interval=")
+				.and_then(|millis| millis.parse().ok())
+				.filter(|millis| *millis > 0)
+				.map(Self::Interval)
+				.ok_or_else(|| {
+					format!("expected `instant`, `manual` or `interval=<ms>`, got `{}`", s)
+				}),
+		}
+	}
+}
+
+/// The `export-sync-spec` command.
+///
+/// Writes the chain spec with its `lightSyncState` set to the latest finalized block of the
+/// local database, the same as the `sync_state_genSyncSpec` RPC method.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct ExportSyncSpecCmd {
+	/// Force raw genesis storage output.
+	#[arg(long)]
+	pub raw: bool,
+
+	/// Write the chain spec to this file instead of stdout.
+	#[arg(long, short, value_name = "PATH")]
+	pub output: Option<std::path::PathBuf>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: sc_cli::DatabaseParams,
+}
+
+impl sc_cli::CliConfiguration for ExportSyncSpecCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&sc_cli::DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+/// The `export-nfts` command.
+///
+/// Writes the NFTs, collections, listings, auctions and rent contracts of a block to
+/// `nfts`, `collections`, `listings`, `auctions` and `rent_contracts` files in the output
+/// directory, with SS58 addresses.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct ExportNftsCmd {
+	/// The block to export, by number or hash. Defaults to the latest finalized block.
+	#[arg(long, value_name = "HASH or NUMBER")]
+	pub at: Option<sc_cli::BlockNumberOrHash>,
+
+	/// The format of the files.
+	#[arg(long, value_enum, default_value_t = ExportFormat::Json)]
+	pub format: ExportFormat,
+
+	/// The directory to write the files to.
+	#[arg(long, short, value_name = "PATH", default_value = ".")]
+	pub output: std::path::PathBuf,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: sc_cli::DatabaseParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: sc_cli::PruningParams,
+}
+
+impl sc_cli::CliConfiguration for ExportNftsCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&sc_cli::DatabaseParams> {
+		Some(&self.database_params)
+	}
+
+	fn pruning_params(&self) -> Option<&sc_cli::PruningParams> {
+		Some(&self.pruning_params)
+	}
+}
+
+/// The format of the files written by `export-nfts`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+	/// One JSON array per file.
+	Json,
+	/// One CSV row per record, with a header row.
+	Csv,
 }
 
+/// The `decode` command.
+///
+/// Decodes SCALE encoded hex with the metadata of the chain and prints it as JSON, without a
+/// running node.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct DecodeCmd {
+	/// What the input is.
+	#[arg(value_enum)]
+	pub kind: DecodeKind,
+
+	/// The hex to decode. For `storage`, the storage key.
+	#[arg(value_name = "HEX")]
+	pub input: String,
+
+	/// The hex of the storage value, to decode along with its key.
+	#[arg(long, value_name = "HEX")]
+	pub value: Option<String>,
+
+	/// The spec version of the runtime that encoded the input. Defaults to the native runtime.
+	#[arg(long)]
+	pub spec_version: Option<u32>,
+
+	/// A file with the metadata of the runtime, as returned by `state_getMetadata`, to decode
+	/// input of another spec version than the native runtime.
+	#[arg(long, value_name = "PATH")]
+	pub metadata: Option<std::path::PathBuf>,
+
+	/// The type of the value, as named in the historical type files.
+	#[arg(long = "type", value_name = "TYPE")]
+	pub type_name: Option<String>,
+
+	/// The directory of the historical `spec_<version>.json` type files.
+	#[arg(long, value_name = "PATH", default_value = "types")]
+	pub types: std::path::PathBuf,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+}
+
+impl sc_cli::CliConfiguration for DecodeCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+}
+
+/// The input of the `decode` command.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub enum DecodeKind {
+	/// A length prefixed extrinsic, signed or not.
+	Extrinsic,
+	/// The value of the `System::Events` storage item.
+	Events,
+	/// A storage key, and its value if given with `--value`.
+	Storage,
+	/// A value of the `--type` type of the historical type files of `--spec-version`, for the
+	/// runtimes that predate the V14 metadata.
+	Value,
+}
+
+/// The `verify-spec` command.
+///
+/// Checks the raw chain spec of `--chain` against the known genesis hash of its network, the wasm
+/// built by `scripts/build_wasm.sh` and the human chain spec it was generated from.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct VerifySpecCmd {
+	/// The genesis hash of the live network. Defaults to the known hash of the live Alphanet or
+	/// Mainnet.
+	#[arg(long, value_name = "HASH")]
+	pub genesis_hash: Option<ternoa_core_primitives::Hash>,
+
+	/// The wasm to compare the `:code` with. Defaults to
+	/// `wasm/<runtime>_runtime.compact.wasm`, then to the wasm embedded in this node.
+	#[arg(long, value_name = "PATH")]
+	pub wasm: Option<std::path::PathBuf>,
+
+	/// The human chain spec to compare the genesis storage with. Defaults to
+	/// `specs/<runtime>/<runtime>_human.json`.
+	#[arg(long, value_name = "PATH")]
+	pub human: Option<std::path::PathBuf>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+}
+
+impl sc_cli::CliConfiguration for VerifySpecCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+}
+
+/// The `key` subcommands, with the Ternoa specific ones on top of Substrate's.
+#[derive(Debug, clap::Subcommand)]
+pub enum KeySubcommand {
+	/// Generate the grandpa, babe, im_online and authority_discovery keys of a validator, insert
+	/// them into the keystore and print the `Session::set_keys` call to submit.
+	GenerateSessionKeys(GenerateSessionKeysCmd),
+
+	#[allow(missing_docs)]
+	#[command(flatten)]
+	Substrate(sc_cli::KeySubcommand),
+}
+
+/// The `key generate-session-keys` command.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct GenerateSessionKeysCmd {
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub keystore_params: sc_cli::KeystoreParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: sc_cli::DatabaseParams,
+}
+
+impl sc_cli::CliConfiguration for GenerateSessionKeysCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+
+	fn keystore_params(&self) -> Option<&sc_cli::KeystoreParams> {
+		Some(&self.keystore_params)
+	}
+
+	fn database_params(&self) -> Option<&sc_cli::DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+/// The `genesis` command.
+///
+/// Builds the chain spec of a new network from a TOML or JSON description of its validators,
+/// balances, governance, assets and Ternoa seed data, see `chain_spec::genesis_file`.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct GenesisCmd {
+	/// The `.toml` or `.json` genesis file.
+	#[arg(value_name = "FILE")]
+	pub file: std::path::PathBuf,
+
+	/// Where to write the human readable chain spec. Defaults to `<id>.json`. It is not written
+	/// when the genesis file has Ternoa seed data, which only the raw chain spec can hold.
+	#[arg(long, value_name = "PATH")]
+	pub human: Option<std::path::PathBuf>,
+
+	/// Where to write the raw chain spec. Defaults to `<id>_raw.json`.
+	#[arg(long, value_name = "PATH")]
+	pub raw: Option<std::path::PathBuf>,
+}
 
 #[derive(Debug, clap::Subcommand)]
 pub enum Subcommand {
 	/// Key management cli utilities
 	#[command(subcommand)]
-	Key(sc_cli::KeySubcommand),
+	Key(KeySubcommand),
 
 	/// Build a chain specification.
 	BuildSpec(sc_cli::BuildSpecCmd),
 
+	/// Build the human and raw chain specifications of a new network from a genesis file.
+	Genesis(GenesisCmd),
+
 	/// Validate blocks.
 	CheckBlock(sc_cli::CheckBlockCmd),
 
+	/// Decode an extrinsic, events or storage from hex, with the metadata of the chain.
+	Decode(DecodeCmd),
+
 	/// Export blocks.
 	ExportBlocks(sc_cli::ExportBlocksCmd),
 
 	/// Export the state of a given block into a chain spec.
 	ExportState(sc_cli::ExportStateCmd),
 
+	/// Export a chain spec checkpointed at the latest finalized block, so that light clients
+	/// can start syncing from there instead of from genesis.
+	ExportSyncSpec(ExportSyncSpecCmd),
+
+	/// Export the NFTs, collections, listings, auctions and rent contracts of a block to JSON or
+	/// CSV files.
+	ExportNfts(ExportNftsCmd),
+
 	/// Import blocks.
 	ImportBlocks(sc_cli::ImportBlocksCmd),
 
@@ -69,6 +363,49 @@ pub enum Subcommand {
 	#[cfg(not(feature = "try-runtime"))]
 	TryRuntime,
 
+	/// Check a raw chain spec against its network genesis, the built runtime and its human spec.
+	VerifySpec(VerifySpecCmd),
+
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Cli, Sealing, Subcommand};
+	use clap::Parser;
+
+	#[test]
+	fn parses_sealing() {
+		assert_eq!("instant".parse::<Sealing>(), Ok(Sealing::Instant));
+		assert_eq!("manual".parse::<Sealing>(), Ok(Sealing::Manual));
+		assert_eq!("interval=6000".parse::<Sealing>(), Ok(Sealing::Interval(6000)));
+	}
+
+	#[test]
+	fn rejects_invalid_sealing() {
+		for mode in ["interval=0", "interval=", "interval=-1", "Instant", "garbage", ""] {
+			assert!(mode.parse::<Sealing>().is_err(), "`{}` should be rejected", mode);
+		}
+	}
+
+	#[test]
+	fn parses_export_sync_spec() {
+		let cli =
+			Cli::try_parse_from(["ternoa", "export-sync-spec", "--chain", "dev", "--raw"]).unwrap();
+		let Some(Subcommand::ExportSyncSpec(cmd)) = cli.subcommand else {
+			panic!("`export-sync-spec` should be parsed as such")
+		};
+		assert!(cmd.raw);
+		assert_eq!(cmd.output, None);
+		assert_eq!(cmd.shared_params.chain.as_deref(), Some("dev"));
+
+		let cli =
+			Cli::try_parse_from(["ternoa", "export-sync-spec", "-o", "sync_spec.json"]).unwrap();
+		let Some(Subcommand::ExportSyncSpec(cmd)) = cli.subcommand else {
+			panic!("`export-sync-spec` should be parsed as such")
+		};
+		assert!(!cmd.raw);
+		assert_eq!(cmd.output, Some("sync_spec.json".into()));
+	}
+}
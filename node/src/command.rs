@@ -1,15 +1,20 @@
 use crate::{
 	chain_spec,
-	cli::{Cli, Subcommand},
-	service,
+	cli::{Cli, GenesisCmd, KeySubcommand, Subcommand},
+	decode, export, service, session_keys, verify_spec,
 };
-use frame_benchmarking_cli::BenchmarkCmd;
+use frame_benchmarking_cli::{BenchmarkCmd, ExtrinsicFactory};
 use sc_cli::{
 	ChainSpec, Error,
 	Result, RevertCmd, RuntimeVersion, SubstrateCli,
 };
 use ternoa_node::service::IdentifyVariant;
 use sc_service::error::Error as ServiceError;
+use sp_keyring::Sr25519Keyring;
+use ternoa_client::benchmarking::{
+	inherent_benchmark_data, RemarkBuilder, TransferKeepAliveBuilder,
+};
+use ternoa_runtime_common::constants::currency::EXISTENTIAL_DEPOSIT;
 
 #[cfg(feature = "alphanet-native")]
 use ternoa_node::service::alphanet_runtime;
@@ -48,7 +53,7 @@ impl SubstrateCli for Cli {
 	}
 
 	fn load_spec(&self, id: &str) -> std::result::Result<Box<dyn sc_service::ChainSpec>, String> {
-		Ok(match id {
+		let chain_spec: Box<dyn sc_service::ChainSpec> = match id {
 			"alphanet" => Box::new(chain_spec::alphanet_config()?),
 			#[cfg(feature = "alphanet-native")]
 			"alphanet-dev" | "a-dev" | "dev" => Box::new(chain_spec::alphanet::development_config()),
@@ -71,7 +76,16 @@ impl SubstrateCli for Cli {
 					chain_spec
 				}
 			},
-		})
+		};
+
+		// Every other command picks the runtime from the id, so reject the ids of no runtime.
+		if (cfg!(feature = "alphanet-native") && chain_spec.is_alphanet()) ||
+			(cfg!(feature = "mainnet-native") && chain_spec.is_mainnet())
+		{
+			Ok(chain_spec)
+		} else {
+			Err(format!("No runtime is available for the chain `{}`", chain_spec.id()))
+		}
 	}
 	fn native_runtime_version(spec: &Box<dyn ChainSpec>) -> &'static RuntimeVersion {
 		#[cfg(feature = "alphanet-native")]
@@ -80,25 +94,89 @@ impl SubstrateCli for Cli {
 		}
 
 		#[cfg(feature = "mainnet-native")]
-		{
+		if spec.is_mainnet() {
 			return &mainnet_runtime::VERSION
 		}
 
-		#[cfg(not(feature = "mainnet-native"))]
-		panic!("No runtime feature (alphanet, mainnet) is enabled");
+		// `load_spec` only accepts the chains of the runtimes the node was built with.
+		panic!("No runtime is available for the chain `{}`", spec.id());
 	}
 }
 
+fn ensure_dev(spec: &Box<dyn sc_service::ChainSpec>) -> Result<()> {
+	if spec.is_dev() {
+		Ok(())
+	} else {
+		panic!("Only Dev Specification Allowed!")
+	}
+}
+
+macro_rules! with_runtime {
+	($chain_spec:expr, $code:expr) => {
+		#[cfg(feature = "alphanet-native")]
+		if $chain_spec.is_alphanet() {
+			#[allow(unused_imports)]
+			use alphanet_runtime::Block;
+			#[allow(unused_imports)]
+			use alphanet_runtime::RuntimeApi;
+			#[allow(unused_imports)]
+			use AlphanetExecutorDispatch as ExecutorDispatch;
+
+			return $code
+		}
+
+		#[cfg(feature = "mainnet-native")]
+		if $chain_spec.is_mainnet() {
+			#[allow(unused_imports)]
+			use mainnet_runtime::Block;
+			#[allow(unused_imports)]
+			use mainnet_runtime::RuntimeApi;
+			#[allow(unused_imports)]
+			use MainnetExecutorDispatch as ExecutorDispatch;
+
+			return $code
+		}
+
+		return Err(format!("No runtime is available for the chain `{}`", $chain_spec.id()).into())
+	};
+}
+
+macro_rules! unwrap_client {
+	(
+		$client:ident,
+		$code:expr
+	) => {
+		match $client.as_ref() {
+			#[cfg(feature = "mainnet-native")]
+			ternoa_client::Client::Mainnet($client) => $code,
+			#[cfg(feature = "alphanet-native")]
+			ternoa_client::Client::Alphanet($client) => $code,
+			#[allow(unreachable_patterns)]
+			_ => Err(Error::Service(ServiceError::Other(
+				"No runtime feature  is enabled".to_string(),
+			))),
+		}
+	};
+}
+
 /// Parse and run command line arguments
 pub fn run() -> sc_cli::Result<()> {
 	let cli = Cli::from_args();
 
 	match &cli.subcommand {
-		Some(Subcommand::Key(cmd)) => cmd.run(&cli),
+		Some(Subcommand::Key(KeySubcommand::GenerateSessionKeys(cmd))) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| {
+				let (client, _, _, _) = service::new_chain_ops(&config)?;
+				session_keys::generate(&config, &client)
+			})
+		},
+		Some(Subcommand::Key(KeySubcommand::Substrate(cmd))) => cmd.run(&cli),
 		Some(Subcommand::BuildSpec(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run(config.chain_spec, config.network))
 		},
+		Some(Subcommand::Genesis(cmd)) => genesis(cmd),
 		Some(Subcommand::CheckBlock(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
@@ -106,6 +184,10 @@ pub fn run() -> sc_cli::Result<()> {
 				Ok((cmd.run(client, import_queue), task_manager))
 			})
 		},
+		Some(Subcommand::Decode(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| decode::run(cmd, &config))
+		},
 		Some(Subcommand::ExportBlocks(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
@@ -120,6 +202,29 @@ pub fn run() -> sc_cli::Result<()> {
 				Ok((cmd.run(client, config.chain_spec), task_manager))
 			})
 		},
+		Some(Subcommand::ExportSyncSpec(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.async_run(|config| {
+				let (sync_spec, task_manager) = service::new_sync_spec(&config, cmd.raw)?;
+				let output = cmd.output.clone();
+				let export = async move {
+					let sync_spec = sync_spec.await?;
+					match output {
+						Some(path) => std::fs::write(path, sync_spec)?,
+						None => println!("{}", sync_spec),
+					}
+					Ok(())
+				};
+				Ok((export, task_manager))
+			})
+		},
+		Some(Subcommand::ExportNfts(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| {
+				let (client, backend, _, _) = service::new_chain_ops(&config)?;
+				export::run(cmd, &config, &client, &backend)
+			})
+		},
 		Some(Subcommand::ImportBlocks(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
@@ -135,36 +240,42 @@ pub fn run() -> sc_cli::Result<()> {
 		Some(Subcommand::Benchmark(cmd)) => benchmark(&cli, cmd),
 		#[cfg(feature = "try-runtime")]
 		Some(Subcommand::TryRuntime(cmd)) => {
-			let runner = cli.create_runner(cmd)?;
+			use sc_executor::{sp_wasm_interface::ExtendedHostFunctions, NativeExecutionDispatch};
+			use ternoa_runtime_common::constants::time::MILLISECS_PER_BLOCK;
+			use try_runtime_cli::block_building_info::timestamp_with_babe_info;
+
+			type HostFunctionsOf<E> = ExtendedHostFunctions<
+				sp_io::SubstrateHostFunctions,
+				<E as NativeExecutionDispatch>::ExtendHostFunctions,
+			>;
 
-			let chain_spec = &runner.config().chain_spec;
+			let runner = cli.create_runner(cmd)?;
+			let chain_spec = runner.config().chain_spec.cloned_box();
 
 			let registry = &runner.config().prometheus_config.as_ref().map(|cfg| &cfg.registry);
 			let task_manager =
-				 			sc_service::TaskManager::new(runner.config().tokio_handle.clone(), *registry)
-						.map_err(|e| sc_cli::Error::Service(sc_service::Error::Prometheus(e)))?;
+				sc_service::TaskManager::new(runner.config().tokio_handle.clone(), *registry)
+					.map_err(|e| sc_cli::Error::Service(sc_service::Error::Prometheus(e)))?;
 
-			#[cfg(feature = "alphanet-native")]
-			if chain_spec.is_cere_dev() {
-				return runner.async_run(|config| {
-					Ok((cmd.run::<service::alphanet_runtime::Block, service::AlphanetExecutorDispatch>(config), task_manager))
-				})
-			}
-
-			#[cfg(feature = "mainnet-native")]
-			{
-				return runner.async_run(|config| {
-					Ok((cmd.run::<service::mainnet_runtime::Block, service::MainnetExecutorDispatch>(config), task_manager))
+			with_runtime!(chain_spec, {
+				runner.async_run(|_| {
+					Ok((
+						cmd.run::<Block, HostFunctionsOf<ExecutorDispatch>, _>(Some(
+							timestamp_with_babe_info(MILLISECS_PER_BLOCK),
+						)),
+						task_manager,
+					))
 				})
-			}
-
-			#[cfg(not(feature = "mainnet-native"))]
-			panic!("No runtime feature (mainnet, alphanet) is enabled")
+			});
 		},
 		#[cfg(not(feature = "try-runtime"))]
 		Some(Subcommand::TryRuntime) => Err("TryRuntime wasn't enabled when building the node. \
 				You can enable it with `--features try-runtime`."
 			.into()),
+		Some(Subcommand::VerifySpec(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| verify_spec::run(cmd, &config))
+		},
 		Some(Subcommand::ChainInfo(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run::<ternoa_core_primitives::Block>(&config))
@@ -172,7 +283,7 @@ pub fn run() -> sc_cli::Result<()> {
 		None => {
 			let runner = cli.create_runner(&cli.run.base)?;
 			runner.run_node_until_exit(|config| async move {
-				service::build_full(config, cli.run.no_hardware_benchmarks)
+				service::build_full(config, cli.run.no_hardware_benchmarks, cli.run.sealing)
 					.map(|full| full.task_manager)
 					.map_err(Error::Service)
 			})
@@ -180,62 +291,6 @@ pub fn run() -> sc_cli::Result<()> {
 	}
 }
 
-fn ensure_dev(spec: &Box<dyn sc_service::ChainSpec>) -> Result<()> {
-	if spec.is_dev() {
-		Ok(())
-	} else {
-		panic!("Only Dev Specification Allowed!")
-	}
-}
-
-macro_rules! with_runtime {
-	($chain_spec:expr, $code:expr) => {
-		#[cfg(feature = "alphanet-native")]
-		if $chain_spec.is_alphanet() {
-			#[allow(unused_imports)]
-			use alphanet_runtime::Block;
-			#[allow(unused_imports)]
-			use alphanet_runtime::RuntimeApi;
-			#[allow(unused_imports)]
-			use AlphanetExecutorDispatch as ExecutorDispatch;
-
-			return $code
-		}
-
-		#[cfg(feature = "mainnet-native")]
-		{
-			#[allow(unused_imports)]
-			use mainnet_runtime::Block;
-			#[allow(unused_imports)]
-			use mainnet_runtime::RuntimeApi;
-			#[allow(unused_imports)]
-			use MainnetExecutorDispatch as ExecutorDispatch;
-
-			return $code
-		}
-
-		#[cfg(not(feature = "mainnet-native"))]
-		panic!("No runtime feature (alphanet, mainnet) is enabled");
-	};
-}
-
-macro_rules! unwrap_client {
-	(
-		$client:ident,
-		$code:expr
-	) => {
-		match $client.as_ref() {
-			#[cfg(feature = "mainnet-native")]
-			ternoa_client::Client::Mainnet($client) => $code,
-			#[cfg(feature = "alphanet-native")]
-			ternoa_client::Client::Alphanet($client) => $code,
-			#[allow(unreachable_patterns)]
-			_ => Err(Error::Service(ServiceError::Other(
-				"No runtime feature  is enabled".to_string(),
-			))),
-		}
-	};
-}
 fn benchmark(cli: &Cli, cmd: &BenchmarkCmd) -> Result<()> {
 	if !cfg!(feature = "runtime-benchmarks") {
 		return Err("Benchmarking wasn't enabled when building the node. \
@@ -257,28 +312,49 @@ fn benchmark(cli: &Cli, cmd: &BenchmarkCmd) -> Result<()> {
 		BenchmarkCmd::Storage(_) =>
 			Err("Storage benchmarking can be enabled with `--features runtime-benchmarks`.".into()),
 		#[cfg(feature = "runtime-benchmarks")]
-		BenchmarkCmd::Storage(_) => {
-			todo!()
-			// with_runtime!(chain_spec, {
-			// 	runner.sync_run(|config| {
-			// 		// ensure that we keep the task manager alive
-			// 		let partial = new_partial::<RuntimeApi, ExecutorDispatch>(&config)?;
-			// 		let db = partial.backend.expose_db();
-			// 		let storage = partial.backend.expose_storage();
-
-			// 		cmd.run(config, partial.client, db, storage)
-			// 	})
-			// });
-		},
-		BenchmarkCmd::Overhead(_cmd) => {
-			print!("BenchmarkCmd::Overhead is not supported");
-			unimplemented!()
-		},
+		BenchmarkCmd::Storage(cmd) => runner.sync_run(|config| {
+			let (client, backend, _, _) = service::new_chain_ops(&config)?;
+			let db = backend.expose_db();
+			let storage = backend.expose_storage();
+
+			unwrap_client!(client, cmd.run(config, client.clone(), db, storage))
+		}),
+		BenchmarkCmd::Overhead(cmd) => runner.sync_run(|config| {
+			let (client, _, _, _) = service::new_chain_ops(&config)?;
+			let remark_builder = RemarkBuilder::new(client.clone());
+
+			unwrap_client!(
+				client,
+				cmd.run(
+					config,
+					client.clone(),
+					inherent_benchmark_data()?,
+					Vec::new(),
+					&remark_builder
+				)
+			)
+		}),
 		BenchmarkCmd::Block(cmd) => runner.sync_run(|config| {
 			let (client, _, _, _) = service::new_chain_ops(&config)?;
 			unwrap_client!(client, cmd.run(client.clone()))
 		}),
-		BenchmarkCmd::Extrinsic(_cmd) => todo!(),
+		BenchmarkCmd::Extrinsic(cmd) => runner.sync_run(|config| {
+			let (client, _, _, _) = service::new_chain_ops(&config)?;
+			// Register the *Remark* and *TKA* builders.
+			let ext_factory = ExtrinsicFactory(vec![
+				Box::new(RemarkBuilder::new(client.clone())),
+				Box::new(TransferKeepAliveBuilder::new(
+					client.clone(),
+					Sr25519Keyring::Alice.to_account_id(),
+					EXISTENTIAL_DEPOSIT,
+				)),
+			]);
+
+			unwrap_client!(
+				client,
+				cmd.run(client.clone(), inherent_benchmark_data()?, Vec::new(), &ext_factory)
+			)
+		}),
 		_ => panic!("Benchmark Command not implement."),
 	}
 }
@@ -293,4 +369,107 @@ fn revert(cli: &Cli, cmd: &RevertCmd) -> Result<()> {
 		});
 		Ok((cmd.run(client, backend, Some(aux_revert)), task_manager))
 	})
-}
\ No newline at end of file
+}
+
+fn genesis(cmd: &GenesisCmd) -> Result<()> {
+	let file = chain_spec::genesis_file::GenesisFile::read(&cmd.file)?;
+	let id = file.id.clone();
+
+	let (human, raw) = match () {
+		#[cfg(feature = "alphanet-native")]
+		_ if id.starts_with("alphanet") => chain_spec::alphanet::chain_specs_from_file(file)?,
+		#[cfg(feature = "mainnet-native")]
+		_ if id.starts_with("mainnet") => chain_spec::mainnet::chain_specs_from_file(file)?,
+		_ => return Err(format!("No runtime is enabled for the chain id `{}`", id).into()),
+	};
+
+	let raw_path = cmd.raw.clone().unwrap_or_else(|| format!("{}_raw.json", id).into());
+	std::fs::write(&raw_path, raw)?;
+	match human {
+		Some(human) => {
+			let human_path = cmd.human.clone().unwrap_or_else(|| format!("{}.json", id).into());
+			std::fs::write(&human_path, human)?;
+			println!("Wrote {} and {}", human_path.display(), raw_path.display());
+		},
+		None => println!(
+			"Wrote {}, without a human chain spec since it cannot hold the Ternoa seed data",
+			raw_path.display()
+		),
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+
+	#[test]
+	fn loads_the_dev_specs_of_the_native_runtimes() {
+		let cli = Cli::parse_from(["ternoa"]);
+
+		#[cfg(feature = "alphanet-native")]
+		for id in ["dev", "a-dev", "alphanet-dev"] {
+			let spec = cli.load_spec(id).unwrap();
+			assert_eq!(spec.id(), "alphanet-dev");
+			assert_eq!(Cli::native_runtime_version(&spec), &alphanet_runtime::VERSION);
+		}
+
+		#[cfg(feature = "mainnet-native")]
+		for id in ["m-dev", "mainnet-dev"] {
+			let spec = cli.load_spec(id).unwrap();
+			assert_eq!(spec.id(), "mainnet-dev");
+			assert_eq!(Cli::native_runtime_version(&spec), &mainnet_runtime::VERSION);
+		}
+
+		assert!(cli.load_spec("").is_err());
+	}
+
+	#[test]
+	#[cfg(feature = "alphanet-native")]
+	fn rejects_chains_without_a_runtime() {
+		let spec = chain_spec::alphanet::development_config();
+		let mut json: serde_json::Value =
+			serde_json::from_str(&spec.as_json(true).unwrap()).unwrap();
+		json["id"] = "kusama".into();
+		let path =
+			std::env::temp_dir().join(format!("ternoa-unknown-chain-{}.json", std::process::id()));
+		std::fs::write(&path, json.to_string()).unwrap();
+
+		let result = Cli::parse_from(["ternoa"]).load_spec(path.to_str().unwrap());
+		std::fs::remove_file(&path).unwrap();
+		assert_eq!(result.err(), Some("No runtime is available for the chain `kusama`".into()));
+	}
+
+	#[test]
+	fn parses_the_benchmark_subcommands() {
+		let parse = |args: &[&str]| {
+			let cli = Cli::try_parse_from(["ternoa", "benchmark"].iter().chain(args)).unwrap();
+			match cli.subcommand {
+				Some(Subcommand::Benchmark(cmd)) => cmd,
+				_ => panic!("`benchmark {}` should be parsed as such", args.join(" ")),
+			}
+		};
+
+		let storage = parse(&["storage", "--dev", "--state-version", "1"]);
+		assert!(matches!(storage, BenchmarkCmd::Storage(_)));
+		let overhead = parse(&["overhead", "--dev"]);
+		assert!(matches!(overhead, BenchmarkCmd::Overhead(_)));
+		let extrinsic =
+			parse(&["extrinsic", "--dev", "--pallet", "system", "--extrinsic", "remark"]);
+		assert!(matches!(extrinsic, BenchmarkCmd::Extrinsic(_)));
+
+		// The runner is never built without the runtime benchmarks.
+		#[cfg(not(feature = "runtime-benchmarks"))]
+		assert!(benchmark(&Cli::parse_from(["ternoa"]), &overhead).is_err());
+	}
+
+	#[test]
+	fn benchmark_inherents_start_at_timestamp_zero() {
+		let inherent_data = inherent_benchmark_data().unwrap();
+		let timestamp: Option<sp_timestamp::Timestamp> =
+			inherent_data.get_data(&sp_timestamp::INHERENT_IDENTIFIER).unwrap();
+		assert_eq!(timestamp, Some(0.into()));
+	}
+}
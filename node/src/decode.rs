@@ -0,0 +1,522 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Offline decoding of extrinsics, events and storage, used by the `decode` command.
+//!
+//! The input is decoded with the metadata of the native runtime of the chain, or with a metadata
+//! file for the other spec versions. The spec versions that predate the V14 metadata have their
+//! types described in the `types/spec_*.json` files, which can decode single values.
+
+use std::path::Path;
+
+use frame_metadata::{
+	v14::{RuntimeMetadataV14, StorageEntryType, StorageHasher},
+	RuntimeMetadataPrefixed,
+};
+use parity_scale_codec::{Compact, Decode, Error as CodecError};
+use sc_service::Configuration;
+use scale_info::TypeDef;
+use serde_json::{json, Map, Value};
+use sp_core::{bytes::from_hex, hexdisplay::HexDisplay, twox_128};
+use ternoa_core_primitives::AccountId;
+use ternoa_node::service::IdentifyVariant;
+
+use crate::{
+	cli::{DecodeCmd, DecodeKind},
+	rpc::events::{number, EventDecoder},
+};
+
+/// Decodes the input of the command and prints it as JSON.
+pub fn run(cmd: &DecodeCmd, config: &Configuration) -> sc_cli::Result<()> {
+	let input = hex(&cmd.input)?;
+
+	let output = match cmd.kind {
+		DecodeKind::Extrinsic => extrinsic(&decoder(cmd, config)?, &input)?,
+		DecodeKind::Events => {
+			let events = decoder(cmd, config)?.events(&input).map_err(|e| e.to_string())?;
+			Value::Array(
+				events
+					.into_iter()
+					.map(|(event, _)| serde_json::to_value(event))
+					.collect::<Result<_, _>>()
+					.map_err(|e| e.to_string())?,
+			)
+		},
+		DecodeKind::Storage => {
+			let value = cmd.value.as_deref().map(hex).transpose()?;
+			storage(&decoder(cmd, config)?, &input, value.as_deref())?
+		},
+		DecodeKind::Value => {
+			let type_name =
+				cmd.type_name.as_deref().ok_or("`--type` is required to decode a value")?;
+			let spec_version = cmd.spec_version.ok_or(
+				"`--spec-version` is required to decode a value with the historical types",
+			)?;
+			LegacyTypes::read(&cmd.types, spec_version)?.decode(type_name, &input)?
+		},
+	};
+
+	println!("{}", serde_json::to_string_pretty(&output).map_err(|e| e.to_string())?);
+
+	Ok(())
+}
+
+fn hex(input: &str) -> Result<Vec<u8>, String> {
+	from_hex(input.trim()).map_err(|e| format!("Invalid hex: {}", e))
+}
+
+/// Returns the decoder of the metadata file if given, of the native runtime otherwise.
+fn decoder(cmd: &DecodeCmd, config: &Configuration) -> Result<EventDecoder, String> {
+	let Some(path) = &cmd.metadata else {
+		let (metadata, native_version) = native_metadata(config)?;
+		return match cmd.spec_version {
+			Some(spec_version) if spec_version != native_version => Err(format!(
+				"This node embeds the metadata of spec version {}, pass the metadata of spec \
+				 version {} with `--metadata`",
+				native_version, spec_version
+			)),
+			_ => EventDecoder::new(metadata),
+		}
+	};
+
+	// `state_getMetadata` returns the metadata as hex, binary files start with the `meta` magic.
+	let content =
+		std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+	let bytes = if content.starts_with(b"meta") {
+		content
+	} else {
+		hex(&String::from_utf8_lossy(&content))?
+	};
+	let metadata = RuntimeMetadataPrefixed::decode(&mut &bytes[..]).map_err(|e| e.to_string())?;
+	let decoder = EventDecoder::new(metadata)?;
+
+	match (cmd.spec_version, spec_version(&decoder.0)) {
+		(Some(expected), Some(actual)) if expected != actual => Err(format!(
+			"{} is the metadata of spec version {}, not {}",
+			path.display(),
+			actual,
+			expected
+		)),
+		_ => Ok(decoder),
+	}
+}
+
+pub fn native_metadata(config: &Configuration) -> Result<(RuntimeMetadataPrefixed, u32), String> {
+	#[cfg(feature = "alphanet-native")]
+	if config.chain_spec.is_alphanet() {
+		return Ok((alphanet_runtime::Runtime::metadata(), alphanet_runtime::VERSION.spec_version))
+	}
+
+	#[cfg(feature = "mainnet-native")]
+	if config.chain_spec.is_mainnet() {
+		return Ok((mainnet_runtime::Runtime::metadata(), mainnet_runtime::VERSION.spec_version))
+	}
+
+	Err("No runtime feature is enabled for this chain".into())
+}
+
+/// Reads the spec version from the `System::Version` constant.
+fn spec_version(metadata: &RuntimeMetadataV14) -> Option<u32> {
+	let system = metadata.pallets.iter().find(|pallet| pallet.name == "System")?;
+	let version = system.constants.iter().find(|constant| constant.name == "Version")?;
+	let (_spec_name, _impl_name, _authoring_version, spec_version) =
+		<(String, String, u32, u32)>::decode(&mut &version.value[..]).ok()?;
+	Some(spec_version)
+}
+
+/// Decodes a length prefixed `UncheckedExtrinsic`, with its signed extensions if it is signed.
+fn extrinsic(decoder: &EventDecoder, mut data: &[u8]) -> Result<Value, String> {
+	let metadata = &decoder.0;
+	let type_param = |name: &str| {
+		metadata
+			.types
+			.resolve(metadata.extrinsic.ty.id)
+			.and_then(|ty| ty.type_params.iter().find(|param| param.name == name))
+			.and_then(|param| param.ty)
+			.map(|ty| ty.id)
+			.ok_or_else(|| format!("The extrinsic type has no `{}` parameter", name))
+	};
+	let input = &mut data;
+	let accounts = &mut Vec::new();
+	let codec_error = |e: CodecError| e.to_string();
+
+	let _len = Compact::<u32>::decode(input).map_err(codec_error)?;
+	let version = u8::decode(input).map_err(codec_error)?;
+	let is_signed = version & 0b1000_0000 != 0;
+	let version = version & 0b0111_1111;
+	if version != metadata.extrinsic.version {
+		return Err(format!("Unsupported extrinsic version {}", version))
+	}
+
+	let signature = if is_signed {
+		let address =
+			decoder.value(type_param("Address")?, input, accounts).map_err(codec_error)?;
+		let signature =
+			decoder.value(type_param("Signature")?, input, accounts).map_err(codec_error)?;
+		let extra = metadata
+			.extrinsic
+			.signed_extensions
+			.iter()
+			.map(|extension| {
+				Ok((extension.identifier.clone(), decoder.value(extension.ty.id, input, accounts)?))
+			})
+			.collect::<Result<Map<_, _>, CodecError>>()
+			.map_err(codec_error)?;
+		json!({ "address": address, "signature": signature, "extra": extra })
+	} else {
+		Value::Null
+	};
+	let call = decoder.value(type_param("Call")?, input, accounts).map_err(codec_error)?;
+
+	if !input.is_empty() {
+		return Err(format!("{} bytes left after the extrinsic", input.len()))
+	}
+
+	Ok(json!({ "version": version, "signature": signature, "call": call }))
+}
+
+/// Finds the storage item of a key and decodes the keys of maps, and the value if given.
+pub fn storage(decoder: &EventDecoder, key: &[u8], value: Option<&[u8]>) -> Result<Value, String> {
+	if key.len() < 32 {
+		return Err("A storage key starts with the hashes of its pallet and item names".into())
+	}
+	let (prefix, mut rest) = key.split_at(32);
+
+	let (pallet, entry) = decoder
+		.0
+		.pallets
+		.iter()
+		.filter_map(|pallet| Some((pallet, pallet.storage.as_ref()?)))
+		.filter(|(_, storage)| twox_128(storage.prefix.as_bytes()) == prefix[..16])
+		.find_map(|(pallet, storage)| {
+			let entry = storage
+				.entries
+				.iter()
+				.find(|entry| twox_128(entry.name.as_bytes()) == prefix[16..])?;
+			Some((pallet, entry))
+		})
+		.ok_or("Unknown storage item")?;
+
+	let (keys, value_type) = match &entry.ty {
+		StorageEntryType::Plain(ty) => (Vec::new(), ty.id),
+		StorageEntryType::Map { hashers, key, value } => {
+			let key_types = match decoder.0.types.resolve(key.id).map(|ty| &ty.type_def) {
+				Some(TypeDef::Tuple(def)) if hashers.len() > 1 =>
+					def.fields.iter().map(|field| field.id).collect(),
+				_ => vec![key.id],
+			};
+			let keys = hashers
+				.iter()
+				.zip(key_types)
+				.map(|(hasher, key_type)| map_key(decoder, hasher, key_type, &mut rest))
+				.collect::<Result<Vec<_>, _>>()
+				.map_err(|e| e.to_string())?;
+			(keys, value.id)
+		},
+	};
+
+	let value = value
+		.map(|mut value| decoder.value(value_type, &mut value, &mut Vec::new()))
+		.transpose()
+		.map_err(|e| e.to_string())?;
+
+	Ok(json!({ "pallet": pallet.name, "item": entry.name, "keys": keys, "value": value }))
+}
+
+/// Decodes the key of a map from its hashed form. Keys behind a non concatenating hasher cannot
+/// be recovered and are returned as the hex of their hash.
+fn map_key(
+	decoder: &EventDecoder,
+	hasher: &StorageHasher,
+	key_type: u32,
+	input: &mut &[u8],
+) -> Result<Value, CodecError> {
+	let (hash_len, is_concat) = match hasher {
+		StorageHasher::Blake2_128 | StorageHasher::Twox128 => (16, false),
+		StorageHasher::Blake2_256 | StorageHasher::Twox256 => (32, false),
+		StorageHasher::Blake2_128Concat => (16, true),
+		StorageHasher::Twox64Concat => (8, true),
+		StorageHasher::Identity => (0, true),
+	};
+	if input.len() < hash_len {
+		return Err("The storage key is too short".into())
+	}
+	let (hash, rest) = input.split_at(hash_len);
+	*input = rest;
+
+	if is_concat {
+		decoder.value(key_type, input, &mut Vec::new())
+	} else {
+		Ok(Value::String(format!("0x{}", HexDisplay::from(&hash))))
+	}
+}
+
+/// The types of a spec version that predates the V14 metadata, in the format of polkadot.js.
+struct LegacyTypes(Map<String, Value>);
+
+impl LegacyTypes {
+	/// Reads `spec_<spec_version>.json` in the given directory.
+	fn read(directory: &Path, spec_version: u32) -> Result<Self, String> {
+		let path = directory.join(format!("spec_{}.json", spec_version));
+		let content = std::fs::read_to_string(&path)
+			.map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+		serde_json::from_str(&content).map(Self).map_err(|e| e.to_string())
+	}
+
+	fn decode(&self, type_name: &str, mut data: &[u8]) -> Result<Value, String> {
+		let input = &mut data;
+		let value = self.value(type_name, input, 0)?;
+		if !input.is_empty() {
+			return Err(format!("{} bytes left after the value", input.len()))
+		}
+		Ok(value)
+	}
+
+	fn value(&self, type_name: &str, input: &mut &[u8], depth: u32) -> Result<Value, String> {
+		if depth > 32 {
+			return Err(format!("The definition of `{}` is too deep", type_name))
+		}
+		let type_name = type_name.trim();
+		if let Some(definition) = self.0.get(type_name) {
+			return self.definition(definition, input, depth + 1)
+		}
+
+		if let Some(item) = generic(type_name, "Vec") {
+			let len = decode::<Compact<u32>>(input)?.0;
+			if item.trim() == "u8" {
+				return bytes(len as usize, input)
+			}
+			return (0..len)
+				.map(|_| self.value(item, input, depth + 1))
+				.collect::<Result<_, _>>()
+				.map(Value::Array)
+		}
+		if let Some(item) = generic(type_name, "Option") {
+			return match decode::<u8>(input)? {
+				0 => Ok(Value::Null),
+				1 => self.value(item, input, depth + 1),
+				_ => Err("Invalid option".into()),
+			}
+		}
+		if generic(type_name, "Compact").is_some() {
+			return Ok(number(decode::<Compact<u128>>(input)?.0))
+		}
+		if let Some(items) = type_name.strip_prefix('(').and_then(|name| name.strip_suffix(')')) {
+			return split_top_level(items)
+				.into_iter()
+				.filter(|item| !item.trim().is_empty())
+				.map(|item| self.value(item, input, depth + 1))
+				.collect::<Result<_, _>>()
+				.map(Value::Array)
+		}
+		if let Some((item, len)) = type_name
+			.strip_prefix('[')
+			.and_then(|name| name.strip_suffix(']'))
+			.and_then(|name| name.rsplit_once(';'))
+		{
+			let len = len.trim().parse().map_err(|_| format!("Invalid array `{}`", type_name))?;
+			if item.trim() == "u8" {
+				return bytes(len, input)
+			}
+			return (0..len)
+				.map(|_| self.value(item, input, depth + 1))
+				.collect::<Result<_, _>>()
+				.map(Value::Array)
+		}
+
+		Ok(match type_name {
+			"bool" => decode::<bool>(input)?.into(),
+			"u8" => decode::<u8>(input)?.into(),
+			"u16" => decode::<u16>(input)?.into(),
+			"u32" | "BlockNumber" => decode::<u32>(input)?.into(),
+			"u64" => decode::<u64>(input)?.into(),
+			"u128" | "Balance" => number(decode::<u128>(input)?),
+			"Text" => decode::<String>(input)?.into(),
+			"Bytes" => {
+				let len = decode::<Compact<u32>>(input)?.0;
+				bytes(len as usize, input)?
+			},
+			"AccountId" => decode::<AccountId>(input)?.to_string().into(),
+			"Hash" | "H256" => bytes(32, input)?,
+			"Null" | "()" => Value::Null,
+			"MultiAddress" => self.definition(
+				&json!({ "_enum": {
+					"Id": "AccountId",
+					"Index": "Compact<u32>",
+					"Raw": "Bytes",
+					"Address32": "[u8; 32]",
+					"Address20": "[u8; 20]"
+				}}),
+				input,
+				depth + 1,
+			)?,
+			"AccountData" => self.definition(
+				&json!({
+					"free": "Balance",
+					"reserved": "Balance",
+					"misc_frozen": "Balance",
+					"fee_frozen": "Balance"
+				}),
+				input,
+				depth + 1,
+			)?,
+			_ => return Err(format!("Unknown type `{}`", type_name)),
+		})
+	}
+
+	/// Decodes a type alias, a struct or an enum of the type files.
+	fn definition(
+		&self,
+		definition: &Value,
+		input: &mut &[u8],
+		depth: u32,
+	) -> Result<Value, String> {
+		let Value::Object(fields) = definition else {
+			return match definition {
+				Value::String(alias) => self.value(alias, input, depth),
+				_ => Err(format!("Invalid type definition {}", definition)),
+			}
+		};
+
+		match fields.get("_enum") {
+			Some(Value::Array(variants)) => {
+				let index = decode::<u8>(input)?;
+				variants
+					.get(index as usize)
+					.cloned()
+					.ok_or_else(|| format!("Unknown variant {}", index))
+			},
+			Some(Value::Object(variants)) => {
+				let index = decode::<u8>(input)?;
+				let (name, variant_type) = variants
+					.iter()
+					.nth(index as usize)
+					.ok_or_else(|| format!("Unknown variant {}", index))?;
+				match variant_type.as_str() {
+					Some("Null") => Ok(Value::String(name.clone())),
+					Some(variant_type) => {
+						let value = self.value(variant_type, input, depth)?;
+						Ok(Value::Object(Map::from_iter([(name.clone(), value)])))
+					},
+					None => Err(format!("Invalid type of variant `{}`", name)),
+				}
+			},
+			Some(_) => Err("Invalid enum definition".into()),
+			None => fields
+				.iter()
+				.map(|(name, field_type)| {
+					let field_type = field_type
+						.as_str()
+						.ok_or_else(|| format!("Invalid type of field `{}`", name))?;
+					Ok((name.clone(), self.value(field_type, input, depth)?))
+				})
+				.collect::<Result<Map<_, _>, String>>()
+				.map(Value::Object),
+		}
+	}
+}
+
+fn decode<T: Decode>(input: &mut &[u8]) -> Result<T, String> {
+	T::decode(input).map_err(|e| e.to_string())
+}
+
+/// Reads `len` bytes and returns them as a hex string.
+fn bytes(len: usize, input: &mut &[u8]) -> Result<Value, String> {
+	if input.len() < len {
+		return Err("Not enough data to fill the buffer".into())
+	}
+	let (bytes, rest) = input.split_at(len);
+	*input = rest;
+	Ok(Value::String(format!("0x{}", HexDisplay::from(&bytes))))
+}
+
+/// Returns the parameter of a generic type such as `Vec<u8>`.
+fn generic<'a>(type_name: &'a str, name: &str) -> Option<&'a str> {
+	type_name.strip_prefix(name)?.strip_prefix('<')?.strip_suffix('>')
+}
+
+/// Splits the items of a tuple on the commas that are not nested in another type.
+fn split_top_level(items: &str) -> Vec<&str> {
+	let mut depth = 0;
+	let mut start = 0;
+	let mut parts = Vec::new();
+	for (i, c) in items.char_indices() {
+		match c {
+			'<' | '(' | '[' => depth += 1,
+			'>' | ')' | ']' => depth -= 1,
+			',' if depth == 0 => {
+				parts.push(&items[start..i]);
+				start = i + 1;
+			},
+			_ => {},
+		}
+	}
+	parts.push(&items[start..]);
+	parts
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_keyring::Sr25519Keyring;
+
+	const ALICE: &str = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+
+	fn alice() -> String {
+		AccountId::from(Sr25519Keyring::Alice.public()).to_string()
+	}
+
+	#[test]
+	fn decodes_multi_address() {
+		let types = LegacyTypes(Map::new());
+
+		let id = hex(&format!("0x00{}", ALICE)).unwrap();
+		assert_eq!(types.decode("MultiAddress", &id).unwrap(), json!({ "Id": alice() }));
+
+		let index = hex("0x01a8").unwrap();
+		assert_eq!(types.decode("MultiAddress", &index).unwrap(), json!({ "Index": 42 }));
+	}
+
+	#[test]
+	fn decodes_nested_tuple() {
+		let types = LegacyTypes(Map::new());
+		let data = hex("0x010000000208abcd010500").unwrap();
+
+		assert_eq!(
+			types.decode("(u32, (u8, Vec<u8>), Option<u16>)", &data).unwrap(),
+			json!([1, [2, "0xabcd"], 5])
+		);
+		assert!(types.decode("(u32, u8)", &data).is_err());
+	}
+
+	#[cfg(feature = "alphanet-native")]
+	#[test]
+	fn decodes_blake2_128_concat_map_key() {
+		let decoder = EventDecoder::new(alphanet_runtime::Runtime::metadata()).unwrap();
+		// `System::Account` of Alice.
+		let key = hex(&format!(
+			"0x26aa394eea5630e07c48ae0c9558cef7b99d880ec681799c0cf30e8886371da9\
+			 de1e86a9a8c739864cf3cc5ec2bea59f{}",
+			ALICE
+		))
+		.unwrap();
+
+		assert_eq!(
+			storage(&decoder, &key, None).unwrap(),
+			json!({ "pallet": "System", "item": "Account", "keys": [alice()], "value": null })
+		);
+	}
+}
@@ -0,0 +1,466 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Snapshots of the NFTs, collections, listings, auctions and rent contracts at a block, written
+//! by the `export-nfts` command.
+//!
+//! The storage of the Ternoa pallets is read by running the native runtime over the state of the
+//! block, and every record is flattened so that it fits in a CSV row.
+
+use std::{fs::File, path::Path, sync::Arc};
+
+use sc_client_api::{Backend, HeaderBackend};
+use sc_service::Configuration;
+use serde::Serialize;
+use sp_api::{CallApiAt, RuntimeVersion};
+use sp_core::crypto::{set_default_ss58_version, Ss58AddressFormat, Ss58Codec};
+use sp_runtime::traits::BlakeTwo256;
+use sp_state_machine::{Ext, OverlayedChanges, StorageTransactionCache};
+use ternoa_client::{AbstractClient, Client, ClientHandle, ExecuteWithClient, FullBackend};
+use ternoa_core_primitives::{AccountId, Balance, Block, BlockNumber, Hash};
+use ternoa_node::service::IdentifyVariant;
+use ternoa_runtime_api::{
+	auction::AuctionState,
+	marketplace::Listing,
+	nft::{CollectionDetails, NftDetails},
+	rent::{RentContract, RentDuration, RentFee},
+};
+
+use crate::cli::{ExportFormat, ExportNftsCmd};
+
+/// The state of the Ternoa pallets, as returned by their runtime APIs.
+struct Snapshot {
+	ss58_prefix: u16,
+	nfts: Vec<NftDetails<AccountId>>,
+	collections: Vec<CollectionDetails<AccountId>>,
+	listings: Vec<Listing<AccountId, Balance>>,
+	auctions: Vec<AuctionState<AccountId, BlockNumber, Balance>>,
+	rent_contracts: Vec<RentContract<AccountId, BlockNumber, Balance>>,
+}
+
+macro_rules! read_snapshot {
+	($runtime:ident) => {{
+		use frame_support::traits::Get;
+		use $runtime::{runtime_apis, Runtime};
+
+		Snapshot {
+			ss58_prefix: <Runtime as frame_system::Config>::SS58Prefix::get().into(),
+			nfts: ternoa_nft::Nfts::<Runtime>::iter()
+				.map(|(nft_id, nft)| runtime_apis::nft_details(nft_id, nft))
+				.collect(),
+			collections: ternoa_nft::Collections::<Runtime>::iter()
+				.map(|(collection_id, collection)| {
+					runtime_apis::collection_details(collection_id, collection)
+				})
+				.collect(),
+			listings: ternoa_marketplace::ListedNfts::<Runtime>::iter()
+				.map(|(nft_id, sale)| runtime_apis::listing(nft_id, sale))
+				.collect(),
+			auctions: ternoa_auction::Auctions::<Runtime>::iter()
+				.map(|(nft_id, auction)| runtime_apis::auction_state(nft_id, auction))
+				.collect(),
+			rent_contracts: ternoa_rent::Contracts::<Runtime>::iter()
+				.map(|(nft_id, contract)| runtime_apis::rent_contract(nft_id, contract))
+				.collect(),
+		}
+	}};
+}
+
+/// Reads the runtime version of a block.
+struct RuntimeVersionAt(Hash);
+
+impl ExecuteWithClient for RuntimeVersionAt {
+	type Output = Result<RuntimeVersion, String>;
+
+	fn execute_with_client<Client, Api, Backend>(self, client: Arc<Client>) -> Self::Output
+	where
+		<Api as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
+		Backend: sc_client_api::Backend<Block> + 'static,
+		Backend::State: sp_api::StateBackend<BlakeTwo256>,
+		Api: ternoa_client::RuntimeApiCollection<StateBackend = Backend::State>,
+		Client: AbstractClient<Block, Backend, Api = Api> + 'static,
+	{
+		client.runtime_version_at(self.0).map_err(|e| e.to_string())
+	}
+}
+
+/// Writes the snapshot of the block selected by `cmd`, the latest finalized one by default.
+///
+/// The storage is read with the types of the native runtime, so the block must run the same spec
+/// version.
+pub fn run(
+	cmd: &ExportNftsCmd,
+	config: &Configuration,
+	client: &Client,
+	backend: &FullBackend,
+) -> sc_cli::Result<()> {
+	let blockchain = backend.blockchain();
+	let hash = match &cmd.at {
+		Some(at) => blockchain
+			.block_hash_from_id(&at.parse::<Block>()?)?
+			.ok_or_else(|| format!("Unknown block {:?}", at))?,
+		None => blockchain.info().finalized_hash,
+	};
+	let number = blockchain.number(hash)?.unwrap_or_default();
+
+	let spec_version = client.execute_with(RuntimeVersionAt(hash))?.spec_version;
+	let native_version = match () {
+		#[cfg(feature = "alphanet-native")]
+		_ if config.chain_spec.is_alphanet() => alphanet_runtime::VERSION.spec_version,
+		#[cfg(feature = "mainnet-native")]
+		_ if config.chain_spec.is_mainnet() => mainnet_runtime::VERSION.spec_version,
+		_ => return Err("No runtime feature is enabled for this chain".into()),
+	};
+	if spec_version != native_version {
+		return Err(format!(
+			"Block #{} runs spec version {} but this node was built with spec version {}, export \
+			 it with a node of the same version",
+			number, spec_version, native_version
+		)
+		.into())
+	}
+
+	let state = backend.state_at(hash).map_err(|e| {
+		format!("The state of block #{} is not available, was it pruned? {}", number, e)
+	})?;
+	let mut overlay = OverlayedChanges::default();
+	let mut cache = StorageTransactionCache::default();
+	let mut ext = Ext::<BlakeTwo256, _>::new(&mut overlay, &mut cache, &state, None);
+
+	let snapshot = sp_externalities::set_and_run_with_externalities(&mut ext, || {
+		#[cfg(feature = "alphanet-native")]
+		if config.chain_spec.is_alphanet() {
+			return Ok(read_snapshot!(alphanet_runtime))
+		}
+
+		#[cfg(feature = "mainnet-native")]
+		if config.chain_spec.is_mainnet() {
+			return Ok(read_snapshot!(mainnet_runtime))
+		}
+
+		Err("No runtime feature is enabled for this chain")
+	})?;
+
+	set_default_ss58_version(Ss58AddressFormat::custom(snapshot.ss58_prefix));
+
+	let directory = &cmd.output;
+	std::fs::create_dir_all(directory)?;
+	let format = cmd.format;
+
+	write(directory, "nfts", format, snapshot.nfts.into_iter().map(NftRecord::from))?;
+	write(
+		directory,
+		"collections",
+		format,
+		snapshot.collections.into_iter().map(CollectionRecord::from),
+	)?;
+	write(directory, "listings", format, snapshot.listings.into_iter().map(ListingRecord::from))?;
+	write(directory, "auctions", format, snapshot.auctions.into_iter().map(AuctionRecord::from))?;
+	write(
+		directory,
+		"rent_contracts",
+		format,
+		snapshot.rent_contracts.into_iter().map(RentContractRecord::from),
+	)?;
+
+	log::info!(
+		"Exported the Ternoa state of block #{} ({:?}) to {}",
+		number,
+		hash,
+		directory.display()
+	);
+
+	Ok(())
+}
+
+/// Writes the records to `<name>.json` or `<name>.csv` in the given directory.
+fn write<R: Serialize>(
+	directory: &Path,
+	name: &str,
+	format: ExportFormat,
+	records: impl Iterator<Item = R>,
+) -> sc_cli::Result<()> {
+	match format {
+		ExportFormat::Json => {
+			let file = File::create(directory.join(format!("{}.json", name)))?;
+			serde_json::to_writer_pretty(file, &records.collect::<Vec<_>>())
+				.map_err(|e| e.to_string())?;
+		},
+		ExportFormat::Csv => {
+			let mut writer = csv::Writer::from_path(directory.join(format!("{}.csv", name)))
+				.map_err(|e| e.to_string())?;
+			for record in records {
+				writer.serialize(record).map_err(|e| e.to_string())?;
+			}
+			writer.flush()?;
+		},
+	}
+
+	Ok(())
+}
+
+fn ss58(account: AccountId) -> String {
+	account.to_ss58check()
+}
+
+fn offchain_data(data: Vec<u8>) -> String {
+	String::from_utf8_lossy(&data).into_owned()
+}
+
+/// An NFT. The royalty is in parts per million.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NftRecord {
+	nft_id: u32,
+	owner: String,
+	creator: String,
+	offchain_data: String,
+	royalty: u32,
+	collection_id: Option<u32>,
+	delegated_to: Option<String>,
+	is_capsule: bool,
+	is_secret: bool,
+	is_soulbound: bool,
+	is_listed: bool,
+	is_rented: bool,
+	is_delegated: bool,
+	is_in_transmission: bool,
+}
+
+impl From<NftDetails<AccountId>> for NftRecord {
+	fn from(nft: NftDetails<AccountId>) -> Self {
+		Self {
+			nft_id: nft.nft_id,
+			owner: ss58(nft.owner),
+			creator: ss58(nft.creator),
+			offchain_data: offchain_data(nft.offchain_data),
+			royalty: nft.royalty.deconstruct(),
+			collection_id: nft.collection_id,
+			delegated_to: nft.delegated_to.map(ss58),
+			is_capsule: nft.state.is_capsule,
+			is_secret: nft.state.is_secret,
+			is_soulbound: nft.state.is_soulbound,
+			is_listed: nft.state.is_listed,
+			is_rented: nft.state.is_rented,
+			is_delegated: nft.state.is_delegated,
+			is_in_transmission: nft.state.is_in_transmission,
+		}
+	}
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CollectionRecord {
+	collection_id: u32,
+	owner: String,
+	offchain_data: String,
+	nft_count: u32,
+	limit: Option<u32>,
+	is_closed: bool,
+}
+
+impl From<CollectionDetails<AccountId>> for CollectionRecord {
+	fn from(collection: CollectionDetails<AccountId>) -> Self {
+		Self {
+			collection_id: collection.collection_id,
+			owner: ss58(collection.owner),
+			offchain_data: offchain_data(collection.offchain_data),
+			nft_count: collection.nfts.len() as u32,
+			limit: collection.limit,
+			is_closed: collection.is_closed,
+		}
+	}
+}
+
+/// A sale offer. Amounts are strings so that they keep their precision.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ListingRecord {
+	nft_id: u32,
+	marketplace_id: u32,
+	seller: String,
+	price: String,
+}
+
+impl From<Listing<AccountId, Balance>> for ListingRecord {
+	fn from(listing: Listing<AccountId, Balance>) -> Self {
+		Self {
+			nft_id: listing.nft_id,
+			marketplace_id: listing.marketplace_id,
+			seller: ss58(listing.seller),
+			price: listing.price.to_string(),
+		}
+	}
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuctionRecord {
+	nft_id: u32,
+	marketplace_id: u32,
+	creator: String,
+	start_block: BlockNumber,
+	end_block: BlockNumber,
+	is_extended: bool,
+	start_price: String,
+	buy_it_price: Option<String>,
+	highest_bidder: Option<String>,
+	highest_bid: Option<String>,
+	bid_count: u32,
+}
+
+impl From<AuctionState<AccountId, BlockNumber, Balance>> for AuctionRecord {
+	fn from(auction: AuctionState<AccountId, BlockNumber, Balance>) -> Self {
+		let (highest_bidder, highest_bid) = match auction.highest_bid {
+			Some((bidder, amount)) => (Some(ss58(bidder)), Some(amount.to_string())),
+			None => (None, None),
+		};
+
+		Self {
+			nft_id: auction.nft_id,
+			marketplace_id: auction.marketplace_id,
+			creator: ss58(auction.creator),
+			start_block: auction.start_block,
+			end_block: auction.end_block,
+			is_extended: auction.is_extended,
+			start_price: auction.start_price.to_string(),
+			buy_it_price: auction.buy_it_price.map(|price| price.to_string()),
+			highest_bidder,
+			highest_bid,
+			bid_count: auction.bidders.len() as u32,
+		}
+	}
+}
+
+/// A rent contract. The fee is either an amount of CAPS or an NFT, and the duration is either
+/// fixed or the period of a subscription.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RentContractRecord {
+	nft_id: u32,
+	renter: String,
+	rentee: Option<String>,
+	start_block: Option<BlockNumber>,
+	is_subscription: bool,
+	duration: BlockNumber,
+	rent_fee: Option<String>,
+	rent_fee_nft_id: Option<u32>,
+	renter_can_revoke: bool,
+	offer_count: u32,
+}
+
+impl From<RentContract<AccountId, BlockNumber, Balance>> for RentContractRecord {
+	fn from(contract: RentContract<AccountId, BlockNumber, Balance>) -> Self {
+		let (is_subscription, duration) = match contract.duration {
+			RentDuration::Fixed(blocks) => (false, blocks),
+			RentDuration::Subscription(period) => (true, period),
+		};
+		let (rent_fee, rent_fee_nft_id) = match contract.rent_fee {
+			RentFee::Tokens(amount) => (Some(amount.to_string()), None),
+			RentFee::Nft(nft_id) => (None, Some(nft_id)),
+		};
+
+		Self {
+			nft_id: contract.nft_id,
+			renter: ss58(contract.renter),
+			rentee: contract.rentee.map(ss58),
+			start_block: contract.start_block,
+			is_subscription,
+			duration,
+			rent_fee,
+			rent_fee_nft_id,
+			renter_can_revoke: contract.renter_can_revoke,
+			offer_count: contract.offers.len() as u32,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+	use sp_keyring::Sr25519Keyring::{Alice, Bob};
+	use sp_runtime::Permill;
+	use ternoa_runtime_api::nft::NftState;
+
+	fn nft() -> NftDetails<AccountId> {
+		NftDetails {
+			nft_id: 3,
+			owner: Alice.to_account_id(),
+			creator: Bob.to_account_id(),
+			offchain_data: b"ipfs://nft".to_vec(),
+			royalty: Permill::from_percent(5),
+			collection_id: None,
+			delegated_to: None,
+			state: NftState { is_listed: true, ..Default::default() },
+		}
+	}
+
+	fn rent_contract() -> RentContract<AccountId, BlockNumber, Balance> {
+		RentContract {
+			nft_id: 3,
+			renter: Alice.to_account_id(),
+			rentee: Some(Bob.to_account_id()),
+			start_block: Some(10),
+			duration: RentDuration::Subscription(100),
+			rent_fee: RentFee::Tokens(10u128.pow(20)),
+			renter_can_revoke: false,
+			offers: vec![Bob.to_account_id()],
+			next_payment: None,
+		}
+	}
+
+	#[test]
+	fn flattens_the_records() {
+		let record = serde_json::to_value(NftRecord::from(nft())).unwrap();
+		assert_eq!(record["owner"], json!(Alice.to_account_id().to_ss58check()));
+		assert_eq!(record["offchainData"], json!("ipfs://nft"));
+		assert_eq!(record["royalty"], json!(50_000));
+		assert_eq!(record["isListed"], json!(true));
+
+		let record = serde_json::to_value(RentContractRecord::from(rent_contract())).unwrap();
+		assert_eq!(record["rentee"], json!(Bob.to_account_id().to_ss58check()));
+		assert_eq!(record["isSubscription"], json!(true));
+		assert_eq!(record["duration"], json!(100));
+		assert_eq!(record["rentFee"], json!("100000000000000000000"));
+		assert_eq!(record["rentFeeNftId"], json!(null));
+		assert_eq!(record["offerCount"], json!(1));
+	}
+
+	#[test]
+	fn writes_json_and_csv_files() {
+		let directory =
+			std::env::temp_dir().join(format!("ternoa-export-nfts-{}", std::process::id()));
+		std::fs::create_dir_all(&directory).unwrap();
+
+		let records =
+			|| [nft(), NftDetails { nft_id: 4, ..nft() }].into_iter().map(NftRecord::from);
+		write(&directory, "nfts", ExportFormat::Json, records()).unwrap();
+		write(&directory, "nfts", ExportFormat::Csv, records()).unwrap();
+		let json = std::fs::read_to_string(directory.join("nfts.json")).unwrap();
+		let csv = std::fs::read_to_string(directory.join("nfts.csv")).unwrap();
+		std::fs::remove_dir_all(&directory).unwrap();
+
+		let json: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+		assert_eq!(json.len(), 2);
+		assert_eq!(json[1]["nftId"], json!(4));
+
+		let lines: Vec<&str> = csv.lines().collect();
+		assert_eq!(lines.len(), 3);
+		assert!(lines[0].starts_with("nftId,owner,creator,offchainData,royalty,collectionId"));
+		assert!(lines[2].starts_with(&format!("4,{},", Alice.to_account_id().to_ss58check())));
+	}
+}
@@ -0,0 +1,715 @@
+//! Index of the NFT events, kept in a RocksDB database under the chain directory.
+//!
+//! The index follows the finalized chain so that it never has to undo a block. The events are
+//! read from `System::Events` and decoded with the metadata of the runtime of each block, the same
+//! way as the events RPC, so the blocks of every past runtime are indexed. Reading them needs the
+//! state of the block: the index only runs on archive nodes and stops at the first block whose
+//! state is missing instead of skipping it.
+
+use std::{collections::BTreeMap, io, path::Path, sync::Arc};
+
+use futures::StreamExt;
+use kvdb::{DBTransaction, KeyValueDB};
+use kvdb_rocksdb::{Database, DatabaseConfig};
+use parity_scale_codec::{Decode, Encode};
+use sc_client_api::BlockchainEvents;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sp_api::{CallApiAt, Metadata, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::crypto::Ss58Codec;
+use sp_runtime::traits::Header;
+use ternoa_core_primitives::{AccountId, Balance, Block, BlockNumber, Hash};
+use ternoa_runtime_api::{
+	marketplace::MarketplaceId,
+	nft::{CollectionId, NFTId},
+};
+
+use crate::rpc::events::{read_events, DecodedEvent, EventDecoder};
+
+const LOG_TARGET: &str = "ternoa-indexer";
+
+/// The maximum number of items returned by a query of the index.
+pub const MAX_PAGE_SIZE: usize = 1_000;
+
+mod columns {
+	/// The number of the last block indexed.
+	pub const META: u32 = 0;
+	/// `nft_id ++ block_number ++ event_index` => `HistoryEntry`.
+	pub const HISTORY: u32 = 1;
+	/// `marketplace_id ++ block_number ++ event_index` => `Sale`.
+	pub const SALES: u32 = 2;
+	/// `account ++ nft_id` => nothing, for every NFT the account has owned.
+	pub const OWNED: u32 = 3;
+	/// `nft_id` => `NftRecord`.
+	pub const NFTS: u32 = 4;
+
+	pub const NUM_COLUMNS: u32 = 5;
+}
+
+const INDEXED_BLOCK_KEY: &[u8] = b"indexed_block";
+
+/// What happened to an NFT.
+#[derive(Encode, Decode, Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum NftEvent<Balance> {
+	Created {
+		owner: AccountId,
+		collection: Option<CollectionId>,
+	},
+	Transferred {
+		from: AccountId,
+		to: AccountId,
+	},
+	Burned,
+	/// The NFT was delegated to an account, or taken back when `to` is `None`.
+	Delegated {
+		to: Option<AccountId>,
+	},
+	AddedToCollection {
+		collection: CollectionId,
+	},
+	Listed {
+		marketplace: MarketplaceId,
+		price: Balance,
+	},
+	Unlisted,
+	/// The NFT was bought through `buy_nft`.
+	Sold {
+		marketplace: MarketplaceId,
+		buyer: AccountId,
+		price: Balance,
+	},
+	#[serde(rename_all = "camelCase")]
+	AuctionCreated {
+		marketplace: MarketplaceId,
+		creator: AccountId,
+		start_price: Balance,
+		buy_it_price: Option<Balance>,
+	},
+	AuctionCancelled,
+	BidAdded {
+		bidder: AccountId,
+		amount: Balance,
+	},
+	BidRemoved {
+		bidder: AccountId,
+		amount: Balance,
+	},
+	/// The auction ended or the NFT was bought at the `buy_it_price`. `winner` is `None` when
+	/// nobody bid, in which case the NFT goes back to the creator of the auction.
+	AuctionCompleted {
+		winner: Option<AccountId>,
+		price: Option<Balance>,
+	},
+	RentContractCreated {
+		renter: AccountId,
+	},
+	RentContractStarted {
+		rentee: AccountId,
+	},
+	/// The contract ended, expired, or was revoked or cancelled.
+	RentContractEnded,
+}
+
+impl<Balance> NftEvent<Balance> {
+	/// Converts every amount with the given function.
+	pub fn map<T>(self, f: impl Fn(Balance) -> T) -> NftEvent<T> {
+		use NftEvent::*;
+
+		match self {
+			Created { owner, collection } => Created { owner, collection },
+			Transferred { from, to } => Transferred { from, to },
+			Burned => Burned,
+			Delegated { to } => Delegated { to },
+			AddedToCollection { collection } => AddedToCollection { collection },
+			Listed { marketplace, price } => Listed { marketplace, price: f(price) },
+			Unlisted => Unlisted,
+			Sold { marketplace, buyer, price } => Sold { marketplace, buyer, price: f(price) },
+			AuctionCreated { marketplace, creator, start_price, buy_it_price } => AuctionCreated {
+				marketplace,
+				creator,
+				start_price: f(start_price),
+				buy_it_price: buy_it_price.map(&f),
+			},
+			AuctionCancelled => AuctionCancelled,
+			BidAdded { bidder, amount } => BidAdded { bidder, amount: f(amount) },
+			BidRemoved { bidder, amount } => BidRemoved { bidder, amount: f(amount) },
+			AuctionCompleted { winner, price } => AuctionCompleted { winner, price: price.map(&f) },
+			RentContractCreated { renter } => RentContractCreated { renter },
+			RentContractStarted { rentee } => RentContractStarted { rentee },
+			RentContractEnded => RentContractEnded,
+		}
+	}
+}
+
+/// An NFT event together with its position among the events of the block.
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct IndexedEvent {
+	event_index: u32,
+	nft_id: NFTId,
+	event: NftEvent<Balance>,
+}
+
+/// An event of an NFT and where it was deposited.
+#[derive(Encode, Decode, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry<Balance> {
+	pub block_number: BlockNumber,
+	pub block_hash: Hash,
+	pub event_index: u32,
+	pub event: NftEvent<Balance>,
+}
+
+impl<Balance> HistoryEntry<Balance> {
+	/// Converts every amount with the given function.
+	pub fn map<T>(self, f: impl Fn(Balance) -> T) -> HistoryEntry<T> {
+		HistoryEntry {
+			block_number: self.block_number,
+			block_hash: self.block_hash,
+			event_index: self.event_index,
+			event: self.event.map(f),
+		}
+	}
+}
+
+/// An NFT bought through a listing or won in an auction.
+#[derive(Encode, Decode, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Sale<Balance> {
+	pub block_number: BlockNumber,
+	pub block_hash: Hash,
+	pub event_index: u32,
+	pub nft_id: NFTId,
+	/// The owner before the sale, unknown when the NFT was minted before the first indexed
+	/// block.
+	pub seller: Option<AccountId>,
+	pub buyer: AccountId,
+	pub price: Balance,
+	pub is_auction: bool,
+}
+
+impl<Balance> Sale<Balance> {
+	/// Converts the price with the given function.
+	pub fn map<T>(self, f: impl Fn(Balance) -> T) -> Sale<T> {
+		Sale {
+			block_number: self.block_number,
+			block_hash: self.block_hash,
+			event_index: self.event_index,
+			nft_id: self.nft_id,
+			seller: self.seller,
+			buyer: self.buyer,
+			price: f(self.price),
+			is_auction: self.is_auction,
+		}
+	}
+}
+
+/// What the index needs to remember about a live NFT to resolve the sales.
+#[derive(Encode, Decode, Clone, Default)]
+struct NftRecord {
+	owner: Option<AccountId>,
+	/// The marketplace of the running auction, if any.
+	auction: Option<MarketplaceId>,
+}
+
+/// The database of the NFT events.
+pub struct Indexer {
+	db: Database,
+}
+
+impl Indexer {
+	/// Opens the index at the given path, creating it if needed.
+	pub fn open(path: &Path) -> io::Result<Self> {
+		let db = Database::open(&DatabaseConfig::with_columns(columns::NUM_COLUMNS), path)?;
+		Ok(Self { db })
+	}
+
+	/// Returns the number of the last block indexed.
+	pub fn indexed_block(&self) -> io::Result<Option<BlockNumber>> {
+		self.db
+			.get(columns::META, INDEXED_BLOCK_KEY)?
+			.map(|value| decode(&value))
+			.transpose()
+	}
+
+	/// Returns at most `limit` events of an NFT, from the oldest to the most recent, starting
+	/// after the event at the given block number and event index.
+	pub fn nft_history(
+		&self,
+		nft_id: NFTId,
+		start_after: Option<(BlockNumber, u32)>,
+		limit: usize,
+	) -> io::Result<Vec<HistoryEntry<Balance>>> {
+		let start = start_after.map(|(block_number, event_index)| {
+			[&nft_id.to_be_bytes()[..], &event_key(block_number, event_index)].concat()
+		});
+		self.db
+			.iter_with_prefix(columns::HISTORY, &nft_id.to_be_bytes())
+			.filter(|entry| match (entry, &start) {
+				(Ok((key, _)), Some(start)) => key[..] > start[..],
+				_ => true,
+			})
+			.take(limit)
+			.map(|entry| decode(&entry?.1))
+			.collect()
+	}
+
+	/// Returns the sales made on a marketplace between two blocks, both included.
+	pub fn sales(
+		&self,
+		marketplace_id: MarketplaceId,
+		from: BlockNumber,
+		to: BlockNumber,
+	) -> io::Result<Vec<Sale<Balance>>> {
+		let mut sales = Vec::new();
+		for entry in self.db.iter_with_prefix(columns::SALES, &marketplace_id.to_be_bytes()) {
+			let (key, value) = entry?;
+			let block_number = decode_be(&key[4..8])?;
+			if block_number > to {
+				break
+			}
+			if block_number >= from {
+				sales.push(decode(&value)?);
+			}
+		}
+
+		Ok(sales)
+	}
+
+	/// Returns at most `limit` of the NFTs that an account has owned, including the ones it no
+	/// longer owns, by increasing id and starting after the given id.
+	pub fn owned_nfts(
+		&self,
+		account: &AccountId,
+		start_after: Option<NFTId>,
+		limit: usize,
+	) -> io::Result<Vec<NFTId>> {
+		let prefix = account.encode();
+		self.db
+			.iter_with_prefix(columns::OWNED, &prefix)
+			.map(|entry| decode_be(&entry?.0[prefix.len()..]))
+			.filter(|nft_id| match (nft_id, start_after) {
+				(Ok(nft_id), Some(start_after)) => *nft_id > start_after,
+				_ => true,
+			})
+			.take(limit)
+			.collect()
+	}
+
+	/// Writes the events of a block and marks it as indexed.
+	fn index_block(
+		&self,
+		block_number: BlockNumber,
+		block_hash: Hash,
+		events: Vec<IndexedEvent>,
+	) -> io::Result<()> {
+		let mut tx = DBTransaction::new();
+		// The records changed by the previous events of the block, which are not written yet.
+		let mut records = BTreeMap::<NFTId, NftRecord>::new();
+
+		for IndexedEvent { event_index, nft_id, event } in events {
+			let key = [&nft_id.to_be_bytes()[..], &event_key(block_number, event_index)].concat();
+			let entry = HistoryEntry { block_number, block_hash, event_index, event };
+			tx.put_vec(columns::HISTORY, &key, entry.encode());
+
+			let record = match records.remove(&nft_id) {
+				Some(record) => record,
+				None => self
+					.db
+					.get(columns::NFTS, &nft_id.to_be_bytes())?
+					.map(|value| decode(&value))
+					.transpose()?
+					.unwrap_or_default(),
+			};
+			let mut sale = |marketplace_id: MarketplaceId, buyer, price, is_auction| {
+				let key =
+					[&marketplace_id.to_be_bytes()[..], &event_key(block_number, event_index)]
+						.concat();
+				let sale = Sale {
+					block_number,
+					block_hash,
+					event_index,
+					nft_id,
+					seller: record.owner.clone(),
+					buyer,
+					price,
+					is_auction,
+				};
+				tx.put_vec(columns::SALES, &key, sale.encode());
+			};
+
+			let record = match entry.event {
+				NftEvent::Created { owner, .. } | NftEvent::Transferred { to: owner, .. } =>
+					NftRecord { owner: Some(owner), ..record },
+				NftEvent::Sold { marketplace, buyer, price } => {
+					sale(marketplace, buyer.clone(), price, false);
+					NftRecord { owner: Some(buyer), ..record }
+				},
+				NftEvent::AuctionCreated { marketplace, .. } =>
+					NftRecord { auction: Some(marketplace), ..record },
+				NftEvent::AuctionCancelled => NftRecord { auction: None, ..record },
+				NftEvent::AuctionCompleted { winner: Some(winner), price: Some(price) } => {
+					if let Some(marketplace) = record.auction {
+						sale(marketplace, winner.clone(), price, true);
+					}
+					NftRecord { owner: Some(winner), auction: None }
+				},
+				NftEvent::AuctionCompleted { .. } => NftRecord { auction: None, ..record },
+				_ => record,
+			};
+			if let Some(owner) = &record.owner {
+				tx.put(columns::OWNED, &[&owner.encode()[..], &nft_id.to_be_bytes()].concat(), &[]);
+			}
+			records.insert(nft_id, record);
+		}
+
+		for (nft_id, record) in records {
+			tx.put_vec(columns::NFTS, &nft_id.to_be_bytes(), record.encode());
+		}
+		tx.put_vec(columns::META, INDEXED_BLOCK_KEY, block_number.encode());
+
+		self.db.write(tx)
+	}
+}
+
+/// Indexes the finalized blocks, starting after the last block indexed.
+pub async fn run<C>(client: Arc<C>, indexer: Arc<Indexer>)
+where
+	C: ProvideRuntimeApi<Block> + CallApiAt<Block> + BlockchainEvents<Block> + HeaderBackend<Block>,
+	C::Api: Metadata<Block>,
+{
+	let mut finality_notifications = client.finality_notification_stream();
+	let mut finalized = client.info().finalized_number;
+	let mut decoder = None;
+
+	loop {
+		if let Err(e) = catch_up(&*client, &indexer, &mut decoder, finalized) {
+			log::error!(target: LOG_TARGET, "Indexing stopped: {}", e);
+			return
+		}
+
+		match finality_notifications.next().await {
+			Some(notification) => finalized = *notification.header.number(),
+			None => return,
+		}
+	}
+}
+
+/// Indexes the blocks up to `finalized`. A block is only marked as indexed once its events are
+/// written, so the blocks that cannot be read are indexed after a restart.
+fn catch_up<C>(
+	client: &C,
+	indexer: &Indexer,
+	decoder: &mut Option<(u32, EventDecoder)>,
+	finalized: BlockNumber,
+) -> Result<(), String>
+where
+	C: ProvideRuntimeApi<Block> + CallApiAt<Block> + HeaderBackend<Block>,
+	C::Api: Metadata<Block>,
+{
+	let start = indexer
+		.indexed_block()
+		.map_err(|e| e.to_string())?
+		.map_or(0, |block_number| block_number + 1);
+
+	for block_number in start..=finalized {
+		let block_hash = client
+			.hash(block_number)
+			.map_err(|e| e.to_string())?
+			.ok_or_else(|| format!("Block #{} not found", block_number))?;
+		let events = read_events(client, decoder, block_hash).map_err(|e| {
+			format!(
+				"Failed to read the events of block #{}: {}. The index needs the state of every \
+				 block, run an archive node synced from genesis",
+				block_number, e
+			)
+		})?;
+		indexer
+			.index_block(block_number, block_hash, nft_events(events))
+			.map_err(|e| e.to_string())?;
+	}
+
+	Ok(())
+}
+
+/// Flattens the NFT events among the events of a block.
+fn nft_events(events: Vec<(DecodedEvent, Vec<AccountId>)>) -> Vec<IndexedEvent> {
+	events
+		.into_iter()
+		.filter_map(|(event, _)| match nft_event(&event) {
+			Ok(nft_event) => nft_event,
+			Err(e) => {
+				log::warn!(
+					target: LOG_TARGET,
+					"Skipped the event {}::{} #{}: {}",
+					event.pallet,
+					event.name,
+					event.event_index,
+					e,
+				);
+				None
+			},
+		})
+		.collect()
+}
+
+/// Reads an event of the NFT pallets from its decoded fields, which keeps working as long as the
+/// fields keep their names, whatever their position in `RuntimeEvent`.
+fn nft_event(event: &DecodedEvent) -> Result<Option<IndexedEvent>, String> {
+	let field =
+		|name: &str| event.fields.get(name).ok_or_else(|| format!("Missing field `{}`", name));
+
+	let nft_event = match (event.pallet.as_str(), event.name.as_str()) {
+		("NFT", "NFTCreated") => NftEvent::Created {
+			owner: account(field("owner")?)?,
+			collection: optional(field("collection_id")?, id)?,
+		},
+		("NFT", "NFTTransferred") => NftEvent::Transferred {
+			from: account(field("sender")?)?,
+			to: account(field("recipient")?)?,
+		},
+		("NFT", "NFTBurned") => NftEvent::Burned,
+		("NFT", "NFTDelegated") =>
+			NftEvent::Delegated { to: optional(field("recipient")?, account)? },
+		("NFT", "NFTAddedToCollection") =>
+			NftEvent::AddedToCollection { collection: id(field("collection_id")?)? },
+		("Marketplace", "NFTListed") => NftEvent::Listed {
+			marketplace: id(field("marketplace_id")?)?,
+			price: balance(field("price")?)?,
+		},
+		("Marketplace", "NFTUnlisted") => NftEvent::Unlisted,
+		("Marketplace", "NFTSold") => NftEvent::Sold {
+			marketplace: id(field("marketplace_id")?)?,
+			buyer: account(field("buyer")?)?,
+			price: balance(field("listed_price")?)?,
+		},
+		("Auction", "AuctionCreated") => NftEvent::AuctionCreated {
+			marketplace: id(field("marketplace_id")?)?,
+			creator: account(field("creator")?)?,
+			start_price: balance(field("start_price")?)?,
+			buy_it_price: optional(field("buy_it_price")?, balance)?,
+		},
+		("Auction", "AuctionCancelled") => NftEvent::AuctionCancelled,
+		("Auction", "BidAdded") => NftEvent::BidAdded {
+			bidder: account(field("bidder")?)?,
+			amount: balance(field("amount")?)?,
+		},
+		("Auction", "BidRemoved") => NftEvent::BidRemoved {
+			bidder: account(field("bidder")?)?,
+			amount: balance(field("amount")?)?,
+		},
+		("Auction", "AuctionCompleted") => NftEvent::AuctionCompleted {
+			winner: optional(field("new_owner")?, account)?,
+			price: optional(field("paid_amount")?, balance)?,
+		},
+		("Rent", "ContractCreated") =>
+			NftEvent::RentContractCreated { renter: account(field("renter")?)? },
+		("Rent", "ContractStarted") =>
+			NftEvent::RentContractStarted { rentee: account(field("rentee")?)? },
+		("Rent", "ContractEnded" | "ContractRevoked" | "ContractExpired" | "ContractCanceled") =>
+			NftEvent::RentContractEnded,
+		_ => return Ok(None),
+	};
+
+	Ok(Some(IndexedEvent {
+		event_index: event.event_index,
+		nft_id: id(field("nft_id")?)?,
+		event: nft_event,
+	}))
+}
+
+/// Reads an account, which the decoder writes in SS58.
+fn account(value: &Value) -> Result<AccountId, String> {
+	value
+		.as_str()
+		.and_then(|account| AccountId::from_ss58check(account).ok())
+		.ok_or_else(|| format!("Invalid account {}", value))
+}
+
+fn id(value: &Value) -> Result<u32, String> {
+	value
+		.as_u64()
+		.and_then(|id| u32::try_from(id).ok())
+		.ok_or_else(|| format!("Invalid id {}", value))
+}
+
+/// Reads an amount, which the decoder writes as a string when it does not fit in a `u64`.
+fn balance(value: &Value) -> Result<Balance, String> {
+	match value {
+		Value::Number(amount) => amount.as_u64().map(Into::into),
+		Value::String(amount) => amount.parse().ok(),
+		_ => None,
+	}
+	.ok_or_else(|| format!("Invalid amount {}", value))
+}
+
+/// Reads an `Option`, which the decoder writes as `"None"` or `{ "Some": value }`.
+fn optional<T>(
+	value: &Value,
+	read: impl Fn(&Value) -> Result<T, String>,
+) -> Result<Option<T>, String> {
+	match value.get("Some") {
+		Some(value) => read(value).map(Some),
+		None if value == "None" => Ok(None),
+		None => Err(format!("Invalid option {}", value)),
+	}
+}
+
+fn event_key(block_number: BlockNumber, event_index: u32) -> [u8; 8] {
+	let mut key = [0; 8];
+	key[..4].copy_from_slice(&block_number.to_be_bytes());
+	key[4..].copy_from_slice(&event_index.to_be_bytes());
+	key
+}
+
+fn decode<T: Decode>(mut value: &[u8]) -> io::Result<T> {
+	T::decode(&mut value).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn decode_be(bytes: &[u8]) -> io::Result<u32> {
+	bytes
+		.try_into()
+		.map(u32::from_be_bytes)
+		.map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Invalid index key"))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+	use sp_keyring::Sr25519Keyring::{Alice, Bob};
+
+	fn decoded(event_index: u32, pallet: &str, name: &str, fields: Value) -> DecodedEvent {
+		DecodedEvent {
+			event_index,
+			extrinsic_index: Some(1),
+			pallet: pallet.into(),
+			name: name.into(),
+			fields,
+		}
+	}
+
+	fn indexed(event_index: u32, nft_id: NFTId, event: NftEvent<Balance>) -> IndexedEvent {
+		IndexedEvent { event_index, nft_id, event }
+	}
+
+	/// Opens an empty index in a directory that is removed once the test is done.
+	fn with_indexer(name: &str, test: impl FnOnce(&Indexer)) {
+		let path =
+			std::env::temp_dir().join(format!("ternoa-indexer-{}-{}", name, std::process::id()));
+		test(&Indexer::open(&path).unwrap());
+		std::fs::remove_dir_all(&path).unwrap();
+	}
+
+	#[test]
+	fn reads_the_nft_events() {
+		let created = decoded(
+			3,
+			"NFT",
+			"NFTCreated",
+			json!({
+				"nft_id": 7,
+				"owner": Alice.to_account_id().to_ss58check(),
+				"offchain_data": "0x00",
+				"royalty": 0,
+				"collection_id": { "Some": 2 },
+				"is_soulbound": false,
+				"mint_fee": "10000000000000000000",
+			}),
+		);
+		assert_eq!(
+			nft_event(&created),
+			Ok(Some(indexed(
+				3,
+				7,
+				NftEvent::Created { owner: Alice.to_account_id(), collection: Some(2) }
+			)))
+		);
+
+		let sold = decoded(
+			4,
+			"Marketplace",
+			"NFTSold",
+			json!({
+				"nft_id": 7,
+				"marketplace_id": 1,
+				"buyer": Bob.to_account_id().to_ss58check(),
+				"listed_price": "10000000000000000000",
+			}),
+		);
+		assert_eq!(
+			nft_event(&sold),
+			Ok(Some(indexed(
+				4,
+				7,
+				NftEvent::Sold {
+					marketplace: 1,
+					buyer: Bob.to_account_id(),
+					price: 10_000_000_000_000_000_000,
+				}
+			)))
+		);
+
+		let completed = decoded(
+			5,
+			"Auction",
+			"AuctionCompleted",
+			json!({ "nft_id": 7, "new_owner": "None", "paid_amount": "None" }),
+		);
+		assert_eq!(
+			nft_event(&completed),
+			Ok(Some(indexed(5, 7, NftEvent::AuctionCompleted { winner: None, price: None })))
+		);
+
+		let transfer = decoded(6, "Balances", "Transfer", json!({ "amount": 1 }));
+		assert_eq!(nft_event(&transfer), Ok(None));
+		let burned = decoded(7, "NFT", "NFTBurned", json!({}));
+		assert_eq!(nft_event(&burned), Err("Missing field `nft_id`".into()));
+	}
+
+	#[test]
+	fn pages_the_history_and_the_owned_nfts() {
+		with_indexer("history", |indexer| {
+			assert_eq!(indexer.indexed_block().unwrap(), None);
+
+			let (alice, bob) = (Alice.to_account_id(), Bob.to_account_id());
+			let created =
+				|owner: &AccountId| NftEvent::Created { owner: owner.clone(), collection: None };
+			let events = vec![
+				indexed(0, 1, created(&alice)),
+				indexed(1, 2, created(&alice)),
+				indexed(2, 3, created(&bob)),
+				indexed(3, 1, NftEvent::Listed { marketplace: 0, price: 100 }),
+			];
+			indexer.index_block(1, Hash::repeat_byte(1), events).unwrap();
+			let sold = NftEvent::Sold { marketplace: 0, buyer: bob.clone(), price: 100 };
+			indexer.index_block(2, Hash::repeat_byte(2), vec![indexed(0, 1, sold)]).unwrap();
+			assert_eq!(indexer.indexed_block().unwrap(), Some(2));
+
+			let history = |start_after, limit| {
+				indexer
+					.nft_history(1, start_after, limit)
+					.unwrap()
+					.into_iter()
+					.map(|entry| (entry.block_number, entry.event_index))
+					.collect::<Vec<_>>()
+			};
+			assert_eq!(history(None, MAX_PAGE_SIZE), vec![(1, 0), (1, 3), (2, 0)]);
+			assert_eq!(history(None, 2), vec![(1, 0), (1, 3)]);
+			assert_eq!(history(Some((1, 3)), 2), vec![(2, 0)]);
+
+			assert_eq!(indexer.owned_nfts(&alice, None, MAX_PAGE_SIZE).unwrap(), vec![1, 2]);
+			assert_eq!(indexer.owned_nfts(&alice, Some(1), MAX_PAGE_SIZE).unwrap(), vec![2]);
+			assert_eq!(indexer.owned_nfts(&bob, None, 1).unwrap(), vec![1]);
+			assert_eq!(indexer.owned_nfts(&bob, Some(1), 1).unwrap(), vec![3]);
+
+			let sales = indexer.sales(0, 1, 2).unwrap();
+			assert_eq!(sales.len(), 1);
+			assert_eq!((sales[0].nft_id, sales[0].block_number), (1, 2));
+			assert_eq!((sales[0].seller.as_ref(), &sales[0].buyer), (Some(&alice), &bob));
+			assert!(!sales[0].is_auction);
+			assert!(indexer.sales(0, 3, 10).unwrap().is_empty());
+		});
+	}
+}
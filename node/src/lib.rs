@@ -14,6 +14,9 @@
 // You should have received a copy of the GNU General Public License
 // along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
 pub mod chain_spec;
+#[cfg(feature = "indexer")]
+mod indexer;
+mod metrics;
 pub mod rpc;
 pub mod service;
 mod cli;
\ No newline at end of file
@@ -21,7 +21,14 @@ mod chain_spec;
 mod service;
 mod cli;
 mod command;
+mod decode;
+mod export;
+#[cfg(feature = "indexer")]
+mod indexer;
+mod metrics;
 mod rpc;
+mod session_keys;
+mod verify_spec;
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
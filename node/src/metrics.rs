@@ -0,0 +1,167 @@
+//! Prometheus gauges for the state of the Ternoa pallets.
+
+use std::{sync::Arc, time::Duration};
+
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_consensus::SyncOracle;
+use substrate_prometheus_endpoint::{
+	register, Gauge, GaugeVec, Opts, PrometheusError, Registry, U64,
+};
+use ternoa_core_primitives::Block;
+use ternoa_runtime_api::metrics::{ChainMetrics, MetricsApi};
+
+const LOG_TARGET: &str = "ternoa-metrics";
+
+/// How often the metrics are read.
+const UPDATE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Gauges over the state of the Ternoa pallets at the finalized block.
+pub struct Metrics {
+	nfts: Gauge<U64>,
+	collections: Gauge<U64>,
+	secret_nfts: Gauge<U64>,
+	capsules: Gauge<U64>,
+	listings: GaugeVec<U64>,
+	auctions: Gauge<U64>,
+	rent_contracts: Gauge<U64>,
+	pending_transmissions: Gauge<U64>,
+	clusters_with_free_slots: Gauge<U64>,
+	pending_bridge_proposals: Gauge<U64>,
+}
+
+impl Metrics {
+	/// Creates the gauges and registers them in the given registry.
+	pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		let gauge = |name: &str, help: &str| register(Gauge::new(name, help)?, registry);
+
+		Ok(Self {
+			nfts: gauge("ternoa_nfts", "Number of NFTs")?,
+			collections: gauge("ternoa_collections", "Number of NFT collections")?,
+			secret_nfts: gauge("ternoa_secret_nfts", "Number of secret NFTs")?,
+			capsules: gauge("ternoa_capsules", "Number of capsules")?,
+			listings: register(
+				GaugeVec::new(
+					Opts::new("ternoa_listings", "Number of NFTs listed for sale per marketplace"),
+					&["marketplace"],
+				)?,
+				registry,
+			)?,
+			auctions: gauge("ternoa_auctions", "Number of live auctions")?,
+			rent_contracts: gauge("ternoa_rent_contracts", "Number of rent contracts")?,
+			pending_transmissions: gauge(
+				"ternoa_pending_transmissions",
+				"Number of NFTs with a pending transmission protocol",
+			)?,
+			clusters_with_free_slots: gauge(
+				"ternoa_tee_clusters_with_free_slots",
+				"Number of TEE clusters with at least one free slot",
+			)?,
+			pending_bridge_proposals: gauge(
+				"ternoa_bridge_pending_proposals",
+				"Number of bridge proposals waiting for relayer votes",
+			)?,
+		})
+	}
+
+	fn update(&self, metrics: ChainMetrics) {
+		self.nfts.set(metrics.nfts.into());
+		self.collections.set(metrics.collections.into());
+		self.secret_nfts.set(metrics.secret_nfts.into());
+		self.capsules.set(metrics.capsules.into());
+		// Drops the marketplaces that no longer have listings.
+		self.listings.reset();
+		for (marketplace_id, count) in metrics.listings {
+			self.listings
+				.with_label_values(&[&marketplace_id.to_string()])
+				.set(count.into());
+		}
+		self.auctions.set(metrics.auctions.into());
+		self.rent_contracts.set(metrics.rent_contracts.into());
+		self.pending_transmissions.set(metrics.pending_transmissions.into());
+		self.clusters_with_free_slots.set(metrics.clusters_with_free_slots.into());
+		self.pending_bridge_proposals.set(metrics.pending_bridge_proposals.into());
+	}
+}
+
+/// Updates the metrics from the finalized block every [`UPDATE_INTERVAL`].
+///
+/// Reading the metrics iterates over most of the Ternoa storage, so it is done on a timer rather
+/// than on every block, and skipped during a major sync.
+pub async fn run<C, S>(client: Arc<C>, sync_oracle: S, metrics: Metrics)
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: MetricsApi<Block>,
+	S: SyncOracle,
+{
+	let mut last_hash = None;
+	loop {
+		futures_timer::Delay::new(UPDATE_INTERVAL).await;
+
+		let hash = client.info().finalized_hash;
+		if last_hash == Some(hash) || sync_oracle.is_major_syncing() {
+			continue
+		}
+
+		match client.runtime_api().chain_metrics(hash) {
+			Ok(chain_metrics) => {
+				metrics.update(chain_metrics);
+				last_hash = Some(hash);
+			},
+			Err(e) => log::debug!(
+				target: LOG_TARGET,
+				"Failed to read the chain metrics at {}: {}",
+				hash,
+				e,
+			),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn listings(registry: &Registry) -> Vec<(String, f64)> {
+		registry
+			.gather()
+			.into_iter()
+			.filter(|family| family.get_name() == "ternoa_listings")
+			.flat_map(|family| family.get_metric().to_vec())
+			.map(|metric| {
+				(metric.get_label()[0].get_value().to_string(), metric.get_gauge().get_value())
+			})
+			.collect()
+	}
+
+	#[test]
+	fn updates_the_gauges() {
+		let registry = Registry::new();
+		let metrics = Metrics::register(&registry).unwrap();
+
+		metrics.update(ChainMetrics {
+			nfts: 12,
+			capsules: 2,
+			listings: vec![(0, 3), (4, 1)],
+			pending_bridge_proposals: 1,
+			..Default::default()
+		});
+		assert_eq!(metrics.nfts.get(), 12);
+		assert_eq!(metrics.capsules.get(), 2);
+		assert_eq!(metrics.secret_nfts.get(), 0);
+		assert_eq!(metrics.pending_bridge_proposals.get(), 1);
+		assert_eq!(listings(&registry), vec![("0".into(), 3.0), ("4".into(), 1.0)]);
+
+		metrics.update(ChainMetrics { nfts: 13, listings: vec![(4, 2)], ..Default::default() });
+		assert_eq!(metrics.nfts.get(), 13);
+		assert_eq!(metrics.pending_bridge_proposals.get(), 0);
+		assert_eq!(listings(&registry), vec![("4".into(), 2.0)]);
+	}
+
+	#[test]
+	fn rejects_a_second_registration() {
+		let registry = Registry::new();
+		Metrics::register(&registry).unwrap();
+		assert!(Metrics::register(&registry).is_err());
+	}
+}
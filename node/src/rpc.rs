@@ -5,20 +5,39 @@
 
 #![warn(missing_docs)]
 
+pub mod auction;
+pub mod bridge;
+pub mod dry_run;
+pub mod events;
+pub mod fees;
+#[cfg(feature = "indexer")]
+pub mod indexer;
+pub mod marketplace;
+pub mod nft;
+pub mod rent;
+pub mod staking_rewards;
+pub mod tee;
+pub mod transmission_protocols;
+
 use std::sync::Arc;
 
-use jsonrpsee::RpcModule;
+use jsonrpsee::{
+	core::Error as JsonRpseeError,
+	types::error::{CallError, ErrorObject},
+	RpcModule,
+};
 use ternoa_core_primitives::{AccountId, Balance, Block, BlockNumber, Hash, Index};
 use sc_client_api::AuxStore;
 use sc_consensus_babe::{BabeConfiguration, Epoch};
 use sc_consensus_epochs::SharedEpochChanges;
+use sc_consensus_manual_seal::EngineCommand;
 use sc_consensus_grandpa::{
 	FinalityProofProvider, GrandpaJustificationStream, SharedAuthoritySet, SharedVoterState,
 };
 use sc_rpc::SubscriptionTaskExecutor;
 pub use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::TransactionPool;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, CallApiAt, Metadata, ProvideRuntimeApi};
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 use sp_consensus::SelectChain;
@@ -60,6 +79,13 @@ pub struct FullDeps<C, P, SC, B> {
 	pub chain_spec: Box<dyn sc_chain_spec::ChainSpec>,
 	/// Whether to deny unsafe calls
 	pub deny_unsafe: DenyUnsafe,
+	/// Sink for the `engine_*` RPC methods, when blocks are sealed on demand.
+	pub command_sink: Option<futures::channel::mpsc::Sender<EngineCommand<Hash>>>,
+	/// Shared statement store reference.
+	pub statement_store: Arc<dyn sp_statement_store::StatementStore>,
+	/// The index of the NFT events.
+	#[cfg(feature = "indexer")]
+	pub indexer: Arc<crate::indexer::Indexer>,
 	/// BABE specific dependencies.
 	pub babe: BabeDeps,
 	/// GRANDPA specific dependencies.
@@ -72,9 +98,11 @@ pub fn create_full<C, P, SC, B>(
 ) -> Result<RpcModule<()>, Box<dyn std::error::Error + Send + Sync>>
 where
 	C: ProvideRuntimeApi<Block>
+		+ CallApiAt<Block>
 		+ sc_client_api::BlockBackend<Block>
 		+ HeaderBackend<Block>
 		+ AuxStore
+		+ sc_client_api::BlockchainEvents<Block>
 		+ HeaderMetadata<Block, Error = BlockChainError>
 		+ Sync
 		+ Send
@@ -83,6 +111,22 @@ where
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: BabeApi<Block>,
 	C::Api: BlockBuilder<Block>,
+	C::Api: Metadata<Block>,
+	C::Api: ApiExt<Block, StateBackend = <C as CallApiAt<Block>>::StateBackend>,
+	C::Api: ternoa_runtime_api::dry_run::DryRunApi<Block>,
+	C::Api: ternoa_runtime_api::fees::FeesApi<Block, AccountId, Balance>,
+	C::Api: ternoa_runtime_api::nft::NftApi<Block, AccountId, Balance>,
+	C::Api: ternoa_runtime_api::marketplace::MarketplaceApi<Block, AccountId, Balance>,
+	C::Api: ternoa_runtime_api::auction::AuctionApi<Block, AccountId, BlockNumber, Balance>,
+	C::Api: ternoa_runtime_api::rent::RentApi<Block, AccountId, BlockNumber, Balance>,
+	C::Api: ternoa_runtime_api::bridge::BridgeApi<Block, AccountId, BlockNumber, Balance>,
+	C::Api: ternoa_runtime_api::staking_rewards::StakingRewardsApi<Block, Balance>,
+	C::Api: ternoa_runtime_api::tee::TeeApi<Block, AccountId, Balance>,
+	C::Api: ternoa_runtime_api::transmission_protocols::TransmissionProtocolsApi<
+		Block,
+		AccountId,
+		BlockNumber,
+	>,
 	P: TransactionPool + 'static,
 	SC: SelectChain<Block> + 'static,
 	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
@@ -91,11 +135,42 @@ where
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use sc_consensus_babe_rpc::{Babe, BabeApiServer};
 	use sc_consensus_grandpa_rpc::{Grandpa, GrandpaApiServer};
+	use sc_consensus_manual_seal::rpc::{ManualSeal, ManualSealApiServer};
+	use sc_rpc::statement::{StatementApiServer, StatementStore};
 	use sc_rpc_spec_v2::chain_spec::{ChainSpec, ChainSpecApiServer};
+	use sc_sync_state_rpc::{SyncState, SyncStateApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 
+	use self::{
+		auction::{Auction, AuctionApiServer},
+		bridge::{Bridge, BridgeApiServer},
+		dry_run::{DryRun, DryRunApiServer},
+		events::{Events, EventsApiServer},
+		fees::{Fees, FeesApiServer},
+		marketplace::{Marketplace, MarketplaceApiServer},
+		nft::{Nft, NftApiServer},
+		rent::{Rent, RentApiServer},
+		staking_rewards::{StakingRewards, StakingRewardsApiServer},
+		tee::{Tee, TeeApiServer},
+		transmission_protocols::{TransmissionProtocols, TransmissionProtocolsApiServer},
+	};
+	#[cfg(feature = "indexer")]
+	use self::indexer::{NftIndex, NftIndexApiServer};
+
 	let mut io = RpcModule::new(());
-	let FullDeps { client, pool, select_chain, chain_spec, deny_unsafe, babe, grandpa } = deps;
+	let FullDeps {
+		client,
+		pool,
+		select_chain,
+		chain_spec,
+		deny_unsafe,
+		command_sink,
+		statement_store,
+		#[cfg(feature = "indexer")]
+		indexer,
+		babe,
+		grandpa,
+	} = deps;
 
 	let BabeDeps { keystore, babe_worker_handle } = babe;
 	let GrandpaDeps {
@@ -122,7 +197,7 @@ where
 	)?;
 	io.merge(
 		Grandpa::new(
-			subscription_executor,
+			subscription_executor.clone(),
 			shared_authority_set.clone(),
 			shared_voter_state,
 			justification_stream,
@@ -131,5 +206,42 @@ where
 		.into_rpc(),
 	)?;
 
+	io.merge(StatementStore::new(statement_store, deny_unsafe).into_rpc())?;
+
+	if let Some(command_sink) = command_sink {
+		io.merge(ManualSeal::new(command_sink).into_rpc())?;
+	}
+
+	io.merge(Nft::new(client.clone()).into_rpc())?;
+	io.merge(Marketplace::new(client.clone()).into_rpc())?;
+	io.merge(Auction::new(client.clone()).into_rpc())?;
+	io.merge(Rent::new(client.clone()).into_rpc())?;
+	io.merge(Tee::new(client.clone()).into_rpc())?;
+	io.merge(StakingRewards::new(client.clone()).into_rpc())?;
+	io.merge(TransmissionProtocols::new(client.clone()).into_rpc())?;
+	io.merge(Bridge::new(client.clone()).into_rpc())?;
+	io.merge(DryRun::new(client.clone(), deny_unsafe).into_rpc())?;
+	io.merge(Events::new(client.clone(), subscription_executor).into_rpc())?;
+	io.merge(Fees::new(client.clone()).into_rpc())?;
+	#[cfg(feature = "indexer")]
+	io.merge(NftIndex::new(indexer).into_rpc())?;
+	io.merge(
+		SyncState::new(chain_spec, client.clone(), shared_authority_set, babe_worker_handle)?
+			.into_rpc(),
+	)?;
+
 	Ok(io)
+}
+
+/// Error code returned when a Ternoa runtime API call fails.
+const RUNTIME_ERROR: i32 = 1;
+
+/// Converts a runtime API error into an RPC error.
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Runtime error",
+		Some(format!("{:?}", err)),
+	))
+	.into()
 }
\ No newline at end of file
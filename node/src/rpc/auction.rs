@@ -0,0 +1,121 @@
+//! RPC interface for the `ternoa_auction` runtime API.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+use ternoa_core_primitives::{AccountId, Balance, BlockNumber};
+use ternoa_runtime_api::{
+	auction::{AuctionApi as AuctionRuntimeApi, AuctionState},
+	nft::NFTId,
+};
+
+use super::runtime_error_into_rpc_err;
+
+/// Auction RPC methods.
+#[rpc(server)]
+pub trait AuctionApi<BlockHash> {
+	/// Returns the auction of the given NFT with its bidders and effective end block.
+	#[method(name = "auction_getAuction")]
+	fn auction(
+		&self,
+		nft_id: NFTId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<AuctionState<AccountId, BlockNumber, NumberOrHex>>>;
+
+	/// Returns all the running auctions.
+	#[method(name = "auction_getAuctions")]
+	fn auctions(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<AuctionState<AccountId, BlockNumber, NumberOrHex>>>;
+
+	/// Returns the amount the given account can claim.
+	#[method(name = "auction_getClaimableBalance")]
+	fn claimable_balance(
+		&self,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<NumberOrHex>;
+
+	/// Returns every account that has something to claim.
+	#[method(name = "auction_getClaimableBalances")]
+	fn claimable_balances(&self, at: Option<BlockHash>)
+		-> RpcResult<Vec<(AccountId, NumberOrHex)>>;
+}
+
+/// Provides RPC methods to query the auctions.
+pub struct Auction<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Auction<C, Block> {
+	/// Creates a new instance of the auction RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> AuctionApiServer<<Block as BlockT>::Hash> for Auction<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: AuctionRuntimeApi<Block, AccountId, BlockNumber, Balance>,
+{
+	fn auction(
+		&self,
+		nft_id: NFTId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<AuctionState<AccountId, BlockNumber, NumberOrHex>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let auction = self
+			.client
+			.runtime_api()
+			.auction(at, nft_id)
+			.map_err(runtime_error_into_rpc_err)?;
+
+		Ok(auction.map(|auction| auction.map(NumberOrHex::from)))
+	}
+
+	fn auctions(
+		&self,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<AuctionState<AccountId, BlockNumber, NumberOrHex>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let auctions =
+			self.client.runtime_api().auctions(at).map_err(runtime_error_into_rpc_err)?;
+
+		Ok(auctions.into_iter().map(|auction| auction.map(NumberOrHex::from)).collect())
+	}
+
+	fn claimable_balance(
+		&self,
+		account: AccountId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<NumberOrHex> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.claimable_balance(at, account)
+			.map(NumberOrHex::from)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn claimable_balances(
+		&self,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<(AccountId, NumberOrHex)>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let claims = self
+			.client
+			.runtime_api()
+			.claimable_balances(at)
+			.map_err(runtime_error_into_rpc_err)?;
+
+		Ok(claims.into_iter().map(|(account, amount)| (account, amount.into())).collect())
+	}
+}
@@ -0,0 +1,83 @@
+//! RPC interface for the `ternoa_bridge` runtime API.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+use ternoa_core_primitives::{AccountId, Balance, BlockNumber};
+use ternoa_runtime_api::bridge::{
+	BridgeApi as BridgeRuntimeApi, BridgeStatus, ChainId, ChainStatus,
+};
+
+use super::runtime_error_into_rpc_err;
+
+/// Bridge RPC methods.
+#[rpc(server)]
+pub trait BridgeApi<BlockHash> {
+	/// Returns the bridge fee, the relayers, the vote threshold and the state of every chain.
+	#[method(name = "bridge_getStatus")]
+	fn bridge_status(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<BridgeStatus<AccountId, BlockNumber, NumberOrHex>>;
+
+	/// Returns the deposit nonce and the open proposals of the given chain.
+	#[method(name = "bridge_getChainStatus")]
+	fn chain_status(
+		&self,
+		chain_id: ChainId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<ChainStatus<AccountId, BlockNumber, NumberOrHex>>>;
+}
+
+/// Provides RPC methods to monitor the bridge proposals.
+pub struct Bridge<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Bridge<C, Block> {
+	/// Creates a new instance of the bridge RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> BridgeApiServer<<Block as BlockT>::Hash> for Bridge<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: BridgeRuntimeApi<Block, AccountId, BlockNumber, Balance>,
+{
+	fn bridge_status(
+		&self,
+		at: Option<Block::Hash>,
+	) -> RpcResult<BridgeStatus<AccountId, BlockNumber, NumberOrHex>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let status = self
+			.client
+			.runtime_api()
+			.bridge_status(at)
+			.map_err(runtime_error_into_rpc_err)?;
+
+		Ok(status.map(NumberOrHex::from))
+	}
+
+	fn chain_status(
+		&self,
+		chain_id: ChainId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<ChainStatus<AccountId, BlockNumber, NumberOrHex>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let status = self
+			.client
+			.runtime_api()
+			.chain_status(at, chain_id)
+			.map_err(runtime_error_into_rpc_err)?;
+
+		Ok(status.map(|status| status.map(NumberOrHex::from)))
+	}
+}
@@ -0,0 +1,205 @@
+//! RPC method to dry-run an extrinsic and report what it would do.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use frame_metadata::{
+	v14::{PalletMetadata, RuntimeMetadataV14},
+	RuntimeMetadata, RuntimeMetadataPrefixed,
+};
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use parity_scale_codec::Decode;
+use sc_rpc_api::DenyUnsafe;
+use scale_info::{form::PortableForm, TypeDef, Variant};
+use serde::{Deserialize, Serialize};
+use sp_api::{ApiExt, CallApiAt, Metadata, ProvideRuntimeApi};
+use sp_block_builder::BlockBuilder;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{traits::Block as BlockT, DispatchError};
+use sp_weights::Weight;
+use ternoa_runtime_api::dry_run::DryRunApi as DryRunRuntimeApi;
+
+use super::runtime_error_into_rpc_err;
+
+/// Error code returned when the extrinsic or the metadata cannot be decoded.
+const DECODE_ERROR: i32 = 2;
+
+/// What an extrinsic would do if it was included on top of a block.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DryRunResult {
+	/// Whether the extrinsic would be included and succeed.
+	pub outcome: DryRunOutcome,
+	/// The events deposited by the extrinsic, in order.
+	pub events: Vec<DryRunEvent>,
+	/// The weight of the dispatched call, after refunds.
+	pub actual_weight: Option<Weight>,
+	/// The storage keys written or removed, excluding child tries.
+	pub written_keys: Vec<Bytes>,
+}
+
+/// Whether the extrinsic would be included and succeed.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum DryRunOutcome {
+	/// The call was dispatched and succeeded.
+	Success,
+	/// The call was dispatched and failed. The fees are still paid.
+	DispatchError { error: DispatchErrorDescription },
+	/// The extrinsic would not be included, e.g. because of a bad nonce or a lack of funds.
+	Invalid { reason: String },
+}
+
+/// A dispatch error resolved against the runtime metadata.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DispatchErrorDescription {
+	/// The pallet that returned the error, for module errors.
+	pub pallet: Option<String>,
+	/// The name of the error variant, or the debug representation of non-module errors.
+	pub error: String,
+	/// The doc comments of the error variant, empty for non-module errors.
+	pub docs: Vec<String>,
+}
+
+/// An event deposited by the extrinsic.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DryRunEvent {
+	/// The name of the pallet that deposited the event.
+	pub pallet: String,
+	/// The name of the event variant.
+	pub name: String,
+	/// The SCALE encoded `RuntimeEvent`.
+	pub data: Bytes,
+}
+
+/// Dry-run RPC methods.
+#[rpc(server)]
+pub trait DryRunApi<BlockHash> {
+	/// Applies the SCALE encoded extrinsic on top of the given block without persisting anything.
+	#[method(name = "dryRun_applyExtrinsic")]
+	fn dry_run(&self, extrinsic: Bytes, at: Option<BlockHash>) -> RpcResult<DryRunResult>;
+}
+
+/// Provides an RPC method to dry-run extrinsics.
+pub struct DryRun<C, Block> {
+	client: Arc<C>,
+	deny_unsafe: DenyUnsafe,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> DryRun<C, Block> {
+	/// Creates a new instance of the dry-run RPC handler.
+	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
+		Self { client, deny_unsafe, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> DryRunApiServer<<Block as BlockT>::Hash> for DryRun<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + CallApiAt<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: BlockBuilder<Block>
+		+ DryRunRuntimeApi<Block>
+		+ Metadata<Block>
+		+ ApiExt<Block, StateBackend = <C as CallApiAt<Block>>::StateBackend>,
+{
+	fn dry_run(&self, extrinsic: Bytes, at: Option<Block::Hash>) -> RpcResult<DryRunResult> {
+		self.deny_unsafe.check_if_safe()?;
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let uxt = Block::Extrinsic::decode(&mut &*extrinsic)
+			.map_err(|err| decode_error("Unable to decode the extrinsic", err))?;
+
+		// All the calls share the overlay of `api`, which is dropped at the end of the method.
+		let api = self.client.runtime_api();
+		let event_count = api.event_count(at).map_err(runtime_error_into_rpc_err)?;
+		let result = api.apply_extrinsic(at, uxt).map_err(runtime_error_into_rpc_err)?;
+		let applied = api.applied_extrinsic(at, event_count).map_err(runtime_error_into_rpc_err)?;
+		let state = self.client.state_at(at).map_err(runtime_error_into_rpc_err)?;
+		let changes = api.into_storage_changes(&state, at).map_err(runtime_error_into_rpc_err)?;
+
+		let metadata =
+			self.client.runtime_api().metadata(at).map_err(runtime_error_into_rpc_err)?;
+		let metadata = match RuntimeMetadataPrefixed::decode(&mut &metadata[..]) {
+			Ok(RuntimeMetadataPrefixed(_, RuntimeMetadata::V14(metadata))) => metadata,
+			Ok(_) => return Err(decode_error("Unsupported metadata version", "expected V14")),
+			Err(err) => return Err(decode_error("Unable to decode the metadata", err)),
+		};
+		let names = Names(metadata);
+
+		let outcome = match result {
+			Ok(Ok(())) => DryRunOutcome::Success,
+			Ok(Err(error)) => DryRunOutcome::DispatchError { error: names.error(error) },
+			Err(error) => DryRunOutcome::Invalid { reason: <&'static str>::from(error).into() },
+		};
+
+		Ok(DryRunResult {
+			outcome,
+			events: applied.events.into_iter().map(|event| names.event(event)).collect(),
+			actual_weight: applied.actual_weight,
+			written_keys: changes
+				.main_storage_changes
+				.into_iter()
+				.map(|(key, _)| key.into())
+				.collect(),
+		})
+	}
+}
+
+/// Resolves the names of events and errors from the runtime metadata.
+struct Names(RuntimeMetadataV14);
+
+impl Names {
+	fn pallet(&self, index: u8) -> Option<&PalletMetadata<PortableForm>> {
+		self.0.pallets.iter().find(|pallet| pallet.index == index)
+	}
+
+	fn variant(&self, type_id: u32, index: u8) -> Option<&Variant<PortableForm>> {
+		match &self.0.types.resolve(type_id)?.type_def {
+			TypeDef::Variant(def) => def.variants.iter().find(|variant| variant.index == index),
+			_ => None,
+		}
+	}
+
+	fn event(&self, data: Vec<u8>) -> DryRunEvent {
+		let pallet = data.first().and_then(|&index| self.pallet(index));
+		let variant = pallet
+			.zip(data.get(1))
+			.and_then(|(pallet, &index)| self.variant(pallet.event.as_ref()?.ty.id, index));
+
+		DryRunEvent {
+			pallet: pallet.map_or_else(|| "Unknown".into(), |pallet| pallet.name.clone()),
+			name: variant.map_or_else(|| "Unknown".into(), |variant| variant.name.clone()),
+			data: data.into(),
+		}
+	}
+
+	fn error(&self, error: DispatchError) -> DispatchErrorDescription {
+		let DispatchError::Module(module_error) = error else {
+			return DispatchErrorDescription {
+				pallet: None,
+				error: format!("{:?}", error),
+				docs: Vec::new(),
+			}
+		};
+		let pallet = self.pallet(module_error.index);
+		let variant = pallet
+			.and_then(|pallet| self.variant(pallet.error.as_ref()?.ty.id, module_error.error[0]));
+
+		DispatchErrorDescription {
+			pallet: pallet.map(|pallet| pallet.name.clone()),
+			error: variant
+				.map_or_else(|| format!("{:?}", module_error), |variant| variant.name.clone()),
+			docs: variant.map(|variant| variant.docs.clone()).unwrap_or_default(),
+		}
+	}
+}
+
+fn decode_error(message: &str, err: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(DECODE_ERROR, message, Some(format!("{:?}", err)))).into()
+}
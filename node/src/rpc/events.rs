@@ -0,0 +1,498 @@
+//! RPC subscription to the events of the chain, filtered on the node.
+//!
+//! The events are decoded from `System::Events` with the metadata of the runtime of each block,
+//! so that any field holding an account can be matched against the filter.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use frame_metadata::{v14::RuntimeMetadataV14, RuntimeMetadata, RuntimeMetadataPrefixed};
+use frame_system::Phase;
+use futures::{future, stream, FutureExt, StreamExt};
+use jsonrpsee::{proc_macros::rpc, types::SubscriptionResult, SubscriptionSink};
+use parity_scale_codec::{Compact, Decode, Error as CodecError};
+use sc_client_api::{backend::StateBackend, BlockchainEvents};
+use sc_rpc::SubscriptionTaskExecutor;
+use scale_info::{form::PortableForm, Field, TypeDef, TypeDefPrimitive};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sp_api::{CallApiAt, Metadata, ProvideRuntimeApi};
+use sp_core::hexdisplay::HexDisplay;
+use sp_runtime::traits::Block as BlockT;
+use ternoa_core_primitives::AccountId;
+
+const LOG_TARGET: &str = "ternoa-rpc-events";
+
+/// The path of the account type in the metadata.
+const ACCOUNT_ID_PATH: [&str; 3] = ["sp_core", "crypto", "AccountId32"];
+
+/// Which events to push. Empty lists match everything.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct EventFilter {
+	/// The names of the pallets, as declared in `construct_runtime`, e.g. `Marketplace`.
+	pub pallets: Vec<String>,
+	/// The names of the events, e.g. `NFTSold`.
+	pub events: Vec<String>,
+	/// Only the events with a field holding one of these accounts.
+	pub accounts: Vec<AccountId>,
+	/// Whether to wait for the blocks to be finalized rather than follow the best chain.
+	pub finalized: bool,
+}
+
+impl EventFilter {
+	fn matches(&self, event: &DecodedEvent, accounts: &[AccountId]) -> bool {
+		(self.pallets.is_empty() || self.pallets.contains(&event.pallet)) &&
+			(self.events.is_empty() || self.events.contains(&event.name)) &&
+			(self.accounts.is_empty() ||
+				accounts.iter().any(|account| self.accounts.contains(account)))
+	}
+}
+
+/// The events of a block that match the filter.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockEvents<Hash> {
+	/// The hash of the block that deposited the events.
+	pub block_hash: Hash,
+	/// The matching events, in the order they were deposited.
+	pub events: Vec<DecodedEvent>,
+}
+
+/// An event and its fields, decoded with the metadata of the runtime.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedEvent {
+	/// The position of the event among all the events of the block.
+	pub event_index: u32,
+	/// The extrinsic that deposited the event, `None` for the events of `on_initialize` and
+	/// `on_finalize`.
+	pub extrinsic_index: Option<u32>,
+	/// The name of the pallet that deposited the event.
+	pub pallet: String,
+	/// The name of the event variant.
+	pub name: String,
+	/// The fields of the event. Accounts are SS58 encoded and byte arrays are hex encoded.
+	pub fields: Value,
+}
+
+/// Event subscription RPC methods.
+#[rpc(server)]
+pub trait EventsApi<BlockHash> {
+	/// Pushes the events that match the filter, once per block that has some.
+	///
+	/// Unless `finalized` is set, the blocks are pushed when they become the best block and may
+	/// be retracted later.
+	#[subscription(
+		name = "events_subscribeBlockEvents" => "events_blockEvents",
+		unsubscribe = "events_unsubscribeBlockEvents",
+		item = BlockEvents<BlockHash>,
+	)]
+	fn subscribe_events(&self, filter: EventFilter);
+}
+
+/// Provides an RPC subscription to the events of the chain.
+pub struct Events<C, Block> {
+	client: Arc<C>,
+	executor: SubscriptionTaskExecutor,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Events<C, Block> {
+	/// Creates a new instance of the events RPC handler.
+	pub fn new(client: Arc<C>, executor: SubscriptionTaskExecutor) -> Self {
+		Self { client, executor, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> EventsApiServer<<Block as BlockT>::Hash> for Events<C, Block>
+where
+	Block: BlockT + 'static,
+	C: ProvideRuntimeApi<Block>
+		+ CallApiAt<Block>
+		+ BlockchainEvents<Block>
+		+ Send
+		+ Sync
+		+ 'static,
+	C::Api: Metadata<Block>,
+{
+	fn subscribe_events(&self, sink: SubscriptionSink, filter: EventFilter) -> SubscriptionResult {
+		let blocks = if filter.finalized {
+			self.client
+				.finality_notification_stream()
+				.map(|notification| {
+					// The blocks finalized implicitly come first.
+					let mut hashes = notification.tree_route.to_vec();
+					hashes.push(notification.hash);
+					hashes
+				})
+				.boxed()
+		} else {
+			self.client
+				.import_notification_stream()
+				.filter(|notification| future::ready(notification.is_new_best))
+				.map(|notification| vec![notification.hash])
+				.boxed()
+		};
+
+		let client = self.client.clone();
+		let mut decoder = None;
+		let stream = blocks
+			.flat_map(stream::iter)
+			.filter_map(move |hash| {
+				let events = match block_events::<_, Block>(&*client, &mut decoder, hash, &filter) {
+					Ok(events) => Some(events).filter(|events| !events.events.is_empty()),
+					Err(e) => {
+						log::debug!(
+							target: LOG_TARGET,
+							"Failed to read the events of {}: {}",
+							hash,
+							e,
+						);
+						None
+					},
+				};
+				future::ready(events)
+			})
+			.boxed();
+
+		let fut = async move {
+			sink.pipe_from_stream(stream).await;
+		};
+		self.executor.spawn("ternoa-rpc-subscription", Some("rpc"), fut.boxed());
+
+		Ok(())
+	}
+}
+
+/// Reads and decodes the events of a block, keeping the events that match the filter.
+///
+/// `decoder` caches the decoder of the last runtime version seen.
+fn block_events<C, Block>(
+	client: &C,
+	decoder: &mut Option<(u32, EventDecoder)>,
+	hash: Block::Hash,
+	filter: &EventFilter,
+) -> Result<BlockEvents<Block::Hash>, String>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + CallApiAt<Block>,
+	C::Api: Metadata<Block>,
+{
+	let events = read_events(client, decoder, hash)?
+		.into_iter()
+		.filter(|(event, accounts)| filter.matches(event, accounts))
+		.map(|(event, _)| event)
+		.collect();
+
+	Ok(BlockEvents { block_hash: hash, events })
+}
+
+/// Reads `System::Events` at a block and decodes it with the metadata of the runtime of the
+/// block, which requires the state of the block.
+///
+/// `decoder` caches the decoder of the last runtime version seen.
+pub(crate) fn read_events<C, Block>(
+	client: &C,
+	decoder: &mut Option<(u32, EventDecoder)>,
+	hash: Block::Hash,
+) -> Result<Vec<(DecodedEvent, Vec<AccountId>)>, String>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + CallApiAt<Block>,
+	C::Api: Metadata<Block>,
+{
+	let spec_version = client.runtime_version_at(hash).map_err(|e| e.to_string())?.spec_version;
+	if decoder.as_ref().map_or(true, |(version, _)| *version != spec_version) {
+		let metadata = client.runtime_api().metadata(hash).map_err(|e| e.to_string())?;
+		let metadata =
+			RuntimeMetadataPrefixed::decode(&mut &metadata[..]).map_err(|e| e.to_string())?;
+		*decoder = Some((spec_version, EventDecoder::new(metadata)?));
+	}
+	let Some((_, decoder)) = decoder else { unreachable!("The decoder is set above; qed") };
+
+	let key = [sp_core::twox_128(b"System"), sp_core::twox_128(b"Events")].concat();
+	let data = client
+		.state_at(hash)
+		.map_err(|e| e.to_string())?
+		.storage(&key)
+		.map_err(|e| format!("{:?}", e))?
+		.unwrap_or_default();
+
+	decoder.events(&data).map_err(|e| e.to_string())
+}
+
+/// Decodes the events and their fields from the metadata of a runtime.
+pub(crate) struct EventDecoder(pub(crate) RuntimeMetadataV14);
+
+impl EventDecoder {
+	pub(crate) fn new(metadata: RuntimeMetadataPrefixed) -> Result<Self, String> {
+		match metadata.1 {
+			RuntimeMetadata::V14(metadata) => Ok(Self(metadata)),
+			_ => Err("Unsupported metadata version".into()),
+		}
+	}
+
+	/// Decodes the SCALE encoded `Vec<EventRecord>` stored in `System::Events`, together with the
+	/// accounts found in each event.
+	pub(crate) fn events(
+		&self,
+		mut data: &[u8],
+	) -> Result<Vec<(DecodedEvent, Vec<AccountId>)>, CodecError> {
+		let input = &mut data;
+		let count = Compact::<u32>::decode(input)?.0;
+
+		(0..count)
+			.map(|event_index| {
+				let phase = Phase::decode(input)?;
+				let pallet_index = u8::decode(input)?;
+				let pallet = self
+					.0
+					.pallets
+					.iter()
+					.find(|pallet| pallet.index == pallet_index)
+					.ok_or("Unknown pallet")?;
+				let event_type = pallet.event.as_ref().ok_or("Pallet without events")?.ty.id;
+				let Some(TypeDef::Variant(def)) =
+					self.0.types.resolve(event_type).map(|ty| &ty.type_def)
+				else {
+					return Err("Invalid event type".into())
+				};
+				let variant_index = u8::decode(input)?;
+				let variant = def
+					.variants
+					.iter()
+					.find(|variant| variant.index == variant_index)
+					.ok_or("Unknown event")?;
+
+				let mut accounts = Vec::new();
+				let fields = self.fields(&variant.fields, input, &mut accounts)?;
+				let _topics = Vec::<[u8; 32]>::decode(input)?;
+
+				let event = DecodedEvent {
+					event_index,
+					extrinsic_index: match phase {
+						Phase::ApplyExtrinsic(index) => Some(index),
+						_ => None,
+					},
+					pallet: pallet.name.clone(),
+					name: variant.name.clone(),
+					fields,
+				};
+				Ok((event, accounts))
+			})
+			.collect()
+	}
+
+	fn fields(
+		&self,
+		fields: &[Field<PortableForm>],
+		input: &mut &[u8],
+		accounts: &mut Vec<AccountId>,
+	) -> Result<Value, CodecError> {
+		match fields {
+			[] => Ok(Value::Null),
+			[field] if field.name.is_none() => self.value(field.ty.id, input, accounts),
+			_ if fields.iter().all(|field| field.name.is_some()) => fields
+				.iter()
+				.map(|field| {
+					let name = field.name.clone().unwrap_or_default();
+					Ok((name, self.value(field.ty.id, input, accounts)?))
+				})
+				.collect::<Result<Map<_, _>, _>>()
+				.map(Value::Object),
+			_ => fields
+				.iter()
+				.map(|field| self.value(field.ty.id, input, accounts))
+				.collect::<Result<_, _>>()
+				.map(Value::Array),
+		}
+	}
+
+	pub(crate) fn value(
+		&self,
+		type_id: u32,
+		input: &mut &[u8],
+		accounts: &mut Vec<AccountId>,
+	) -> Result<Value, CodecError> {
+		let ty = self.0.types.resolve(type_id).ok_or("Unknown type")?;
+		if ty.path.segments == ACCOUNT_ID_PATH {
+			let account = AccountId::decode(input)?;
+			let value = Value::String(account.to_string());
+			accounts.push(account);
+			return Ok(value)
+		}
+
+		match &ty.type_def {
+			TypeDef::Composite(def) => self.fields(&def.fields, input, accounts),
+			TypeDef::Variant(def) => {
+				let index = u8::decode(input)?;
+				let variant = def
+					.variants
+					.iter()
+					.find(|variant| variant.index == index)
+					.ok_or("Unknown variant")?;
+				if variant.fields.is_empty() {
+					return Ok(Value::String(variant.name.clone()))
+				}
+				let fields = self.fields(&variant.fields, input, accounts)?;
+				Ok(Value::Object(Map::from_iter([(variant.name.clone(), fields)])))
+			},
+			TypeDef::Sequence(def) => {
+				let len = Compact::<u32>::decode(input)?.0;
+				self.items(def.type_param.id, len, input, accounts)
+			},
+			TypeDef::Array(def) => self.items(def.type_param.id, def.len, input, accounts),
+			TypeDef::Tuple(def) => def
+				.fields
+				.iter()
+				.map(|field| self.value(field.id, input, accounts))
+				.collect::<Result<_, _>>()
+				.map(Value::Array),
+			TypeDef::Primitive(def) => primitive(def, input),
+			TypeDef::Compact(_) => Ok(number(Compact::<u128>::decode(input)?.0)),
+			TypeDef::BitSequence(_) => Err("Bit sequences are not supported".into()),
+		}
+	}
+
+	/// Decodes the items of a sequence or an array. Bytes are returned as a hex string.
+	fn items(
+		&self,
+		type_id: u32,
+		len: u32,
+		input: &mut &[u8],
+		accounts: &mut Vec<AccountId>,
+	) -> Result<Value, CodecError> {
+		let is_byte = matches!(
+			self.0.types.resolve(type_id).map(|ty| &ty.type_def),
+			Some(TypeDef::Primitive(TypeDefPrimitive::U8))
+		);
+		if is_byte {
+			let len = len as usize;
+			if input.len() < len {
+				return Err("Not enough data to fill the buffer".into())
+			}
+			let (bytes, rest) = input.split_at(len);
+			*input = rest;
+			return Ok(Value::String(format!("0x{}", HexDisplay::from(&bytes))))
+		}
+
+		(0..len)
+			.map(|_| self.value(type_id, input, accounts))
+			.collect::<Result<_, _>>()
+			.map(Value::Array)
+	}
+}
+
+fn primitive(def: &TypeDefPrimitive, input: &mut &[u8]) -> Result<Value, CodecError> {
+	Ok(match def {
+		TypeDefPrimitive::Bool => bool::decode(input)?.into(),
+		TypeDefPrimitive::Char =>
+			char::from_u32(u32::decode(input)?).ok_or("Invalid char")?.to_string().into(),
+		TypeDefPrimitive::Str => String::decode(input)?.into(),
+		TypeDefPrimitive::U8 => u8::decode(input)?.into(),
+		TypeDefPrimitive::U16 => u16::decode(input)?.into(),
+		TypeDefPrimitive::U32 => u32::decode(input)?.into(),
+		TypeDefPrimitive::U64 => u64::decode(input)?.into(),
+		TypeDefPrimitive::U128 => number(u128::decode(input)?),
+		TypeDefPrimitive::I8 => i8::decode(input)?.into(),
+		TypeDefPrimitive::I16 => i16::decode(input)?.into(),
+		TypeDefPrimitive::I32 => i32::decode(input)?.into(),
+		TypeDefPrimitive::I64 => i64::decode(input)?.into(),
+		TypeDefPrimitive::I128 => i128::decode(input)?.to_string().into(),
+		TypeDefPrimitive::U256 | TypeDefPrimitive::I256 =>
+			format!("0x{}", HexDisplay::from(&<[u8; 32]>::decode(input)?)).into(),
+	})
+}
+
+/// Returns amounts that do not fit in a JSON number as strings.
+pub(crate) fn number(value: u128) -> Value {
+	u64::try_from(value).map_or_else(|_| value.to_string().into(), Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use parity_scale_codec::Encode;
+	use serde_json::json;
+	use sp_core::H256;
+	use sp_keyring::Sr25519Keyring::{Alice, Bob};
+
+	fn decoded(pallet: &str, name: &str) -> DecodedEvent {
+		DecodedEvent {
+			event_index: 0,
+			extrinsic_index: None,
+			pallet: pallet.into(),
+			name: name.into(),
+			fields: Value::Null,
+		}
+	}
+
+	#[test]
+	fn filters_the_events() {
+		let remarked = decoded("System", "Remarked");
+		let sold = decoded("Marketplace", "NFTSold");
+		let bob = [Bob.to_account_id()];
+
+		let all = EventFilter::default();
+		assert!(all.matches(&remarked, &[]) && all.matches(&sold, &bob));
+
+		let marketplace = EventFilter { pallets: vec!["Marketplace".into()], ..Default::default() };
+		assert!(!marketplace.matches(&remarked, &bob) && marketplace.matches(&sold, &bob));
+
+		let sales_of_alice = EventFilter {
+			events: vec!["NFTSold".into()],
+			accounts: vec![Alice.to_account_id()],
+			..Default::default()
+		};
+		assert!(!sales_of_alice.matches(&sold, &bob));
+		assert!(sales_of_alice.matches(&sold, &[Bob.to_account_id(), Alice.to_account_id()]));
+	}
+
+	#[cfg(feature = "alphanet-native")]
+	#[test]
+	fn decodes_the_events_and_their_accounts() {
+		use alphanet_runtime::RuntimeEvent;
+		use frame_system::{Event, EventRecord};
+
+		let record = |phase, event| EventRecord { phase, event, topics: Vec::<H256>::new() };
+		let records = vec![
+			record(
+				Phase::Initialization,
+				RuntimeEvent::System(Event::NewAccount { account: Alice.to_account_id() }),
+			),
+			record(
+				Phase::ApplyExtrinsic(1),
+				RuntimeEvent::System(Event::Remarked {
+					sender: Bob.to_account_id(),
+					hash: H256::repeat_byte(0xab),
+				}),
+			),
+		];
+
+		let decoder = EventDecoder::new(alphanet_runtime::Runtime::metadata()).unwrap();
+		let events = decoder.events(&records.encode()).unwrap();
+		assert_eq!(events.len(), 2);
+
+		let (new_account, accounts) = &events[0];
+		assert_eq!((new_account.event_index, new_account.extrinsic_index), (0, None));
+		assert_eq!(
+			(new_account.pallet.as_str(), new_account.name.as_str()),
+			("System", "NewAccount")
+		);
+		assert_eq!(new_account.fields, json!({ "account": Alice.to_account_id().to_string() }));
+		assert_eq!(accounts, &[Alice.to_account_id()]);
+
+		let (remarked, accounts) = &events[1];
+		assert_eq!((remarked.event_index, remarked.extrinsic_index), (1, Some(1)));
+		assert_eq!(
+			remarked.fields,
+			json!({
+				"sender": Bob.to_account_id().to_string(),
+				"hash": format!("0x{}", "ab".repeat(32)),
+			})
+		);
+		assert_eq!(accounts, &[Bob.to_account_id()]);
+
+		assert!(decoder.events(&records.encode()[..20]).is_err());
+	}
+}
@@ -0,0 +1,61 @@
+//! RPC interface for the Ternoa fees runtime API.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+use ternoa_core_primitives::{AccountId, Balance};
+use ternoa_runtime_api::fees::{CostEstimate, FeesApi as FeesRuntimeApi};
+
+use super::runtime_error_into_rpc_err;
+
+/// Fees RPC methods.
+#[rpc(server)]
+pub trait FeesApi<BlockHash> {
+	/// Returns every amount the SCALE encoded call takes from its signer, by destination.
+	#[method(name = "fees_getCostEstimate")]
+	fn cost_estimate(
+		&self,
+		encoded_call: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<CostEstimate<AccountId, NumberOrHex>>>;
+}
+
+/// Provides RPC methods to estimate the total cost of a call.
+pub struct Fees<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Fees<C, Block> {
+	/// Creates a new instance of the fees RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> FeesApiServer<<Block as BlockT>::Hash> for Fees<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: FeesRuntimeApi<Block, AccountId, Balance>,
+{
+	fn cost_estimate(
+		&self,
+		encoded_call: Bytes,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<CostEstimate<AccountId, NumberOrHex>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let estimate = self
+			.client
+			.runtime_api()
+			.cost_estimate(at, encoded_call.to_vec())
+			.map_err(runtime_error_into_rpc_err)?;
+
+		Ok(estimate.map(|estimate| estimate.map(NumberOrHex::from)))
+	}
+}
@@ -0,0 +1,131 @@
+//! RPC interface for the index of the NFT events.
+
+use std::sync::Arc;
+
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sp_rpc::number::NumberOrHex;
+use ternoa_core_primitives::{AccountId, BlockNumber};
+use ternoa_runtime_api::{marketplace::MarketplaceId, nft::NFTId};
+
+use crate::indexer::{HistoryEntry, Indexer, Sale, MAX_PAGE_SIZE};
+
+/// Error code returned when the index cannot be read.
+const INDEX_ERROR: i32 = 3;
+
+/// NFT index RPC methods.
+#[rpc(server)]
+pub trait NftIndexApi {
+	/// Returns at most `limit` events of the given NFT, from the oldest to the most recent,
+	/// starting after the event at the given `[block_number, event_index]`.
+	#[method(name = "nftIndex_getNftHistory")]
+	fn nft_history(
+		&self,
+		nft_id: NFTId,
+		start_after: Option<(BlockNumber, u32)>,
+		limit: Option<u32>,
+	) -> RpcResult<Vec<HistoryEntry<NumberOrHex>>>;
+
+	/// Returns the sales made on the given marketplace between two blocks, both included.
+	#[method(name = "nftIndex_getMarketplaceSales")]
+	fn marketplace_sales(
+		&self,
+		marketplace_id: MarketplaceId,
+		from: Option<BlockNumber>,
+		to: Option<BlockNumber>,
+	) -> RpcResult<Vec<Sale<NumberOrHex>>>;
+
+	/// Returns at most `limit` of the NFTs the given account has owned, including the ones it no
+	/// longer owns, by increasing id and starting after the given id.
+	#[method(name = "nftIndex_getOwnedNfts")]
+	fn owned_nfts(
+		&self,
+		account: AccountId,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+	) -> RpcResult<Vec<NFTId>>;
+
+	/// Returns the number of the last block indexed.
+	#[method(name = "nftIndex_getIndexedBlock")]
+	fn indexed_block(&self) -> RpcResult<Option<BlockNumber>>;
+}
+
+/// Provides RPC methods to query the index of the NFT events.
+pub struct NftIndex {
+	indexer: Arc<Indexer>,
+}
+
+impl NftIndex {
+	/// Creates a new instance of the NFT index RPC handler.
+	pub fn new(indexer: Arc<Indexer>) -> Self {
+		Self { indexer }
+	}
+}
+
+impl NftIndexApiServer for NftIndex {
+	fn nft_history(
+		&self,
+		nft_id: NFTId,
+		start_after: Option<(BlockNumber, u32)>,
+		limit: Option<u32>,
+	) -> RpcResult<Vec<HistoryEntry<NumberOrHex>>> {
+		let history = self
+			.indexer
+			.nft_history(nft_id, start_after, page_size(limit))
+			.map_err(index_error)?;
+		Ok(history.into_iter().map(|entry| entry.map(NumberOrHex::from)).collect())
+	}
+
+	fn marketplace_sales(
+		&self,
+		marketplace_id: MarketplaceId,
+		from: Option<BlockNumber>,
+		to: Option<BlockNumber>,
+	) -> RpcResult<Vec<Sale<NumberOrHex>>> {
+		let sales = self
+			.indexer
+			.sales(marketplace_id, from.unwrap_or_default(), to.unwrap_or(BlockNumber::MAX))
+			.map_err(index_error)?;
+		Ok(sales.into_iter().map(|sale| sale.map(NumberOrHex::from)).collect())
+	}
+
+	fn owned_nfts(
+		&self,
+		account: AccountId,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+	) -> RpcResult<Vec<NFTId>> {
+		self.indexer
+			.owned_nfts(&account, start_after, page_size(limit))
+			.map_err(index_error)
+	}
+
+	fn indexed_block(&self) -> RpcResult<Option<BlockNumber>> {
+		self.indexer.indexed_block().map_err(index_error)
+	}
+}
+
+/// Caps the requested page size, which defaults to the largest page.
+fn page_size(limit: Option<u32>) -> usize {
+	limit.map_or(MAX_PAGE_SIZE, |limit| (limit as usize).min(MAX_PAGE_SIZE))
+}
+
+fn index_error(err: std::io::Error) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(INDEX_ERROR, "Index error", Some(err.to_string()))).into()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn caps_the_page_size() {
+		assert_eq!(page_size(None), MAX_PAGE_SIZE);
+		assert_eq!(page_size(Some(0)), 0);
+		assert_eq!(page_size(Some(10)), 10);
+		assert_eq!(page_size(Some(u32::MAX)), MAX_PAGE_SIZE);
+	}
+}
@@ -0,0 +1,93 @@
+//! RPC interface for the `ternoa_marketplace` runtime API.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+use ternoa_core_primitives::{AccountId, Balance};
+use ternoa_runtime_api::{
+	marketplace::{BuyQuote, Listing, MarketplaceApi as MarketplaceRuntimeApi, MarketplaceId},
+	nft::NFTId,
+	Page, MAX_PAGE_SIZE,
+};
+
+use super::runtime_error_into_rpc_err;
+
+/// Marketplace RPC methods.
+#[rpc(server)]
+pub trait MarketplaceApi<BlockHash> {
+	/// Returns the NFTs listed on the given marketplace among at most `limit` listings, starting
+	/// after the NFT `start_after`.
+	#[method(name = "marketplace_getListings")]
+	fn listings(
+		&self,
+		marketplace_id: MarketplaceId,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Page<NFTId, Listing<AccountId, NumberOrHex>>>;
+
+	/// Returns the seller proceeds, marketplace commission, royalty and transaction fee of
+	/// buying the given NFT.
+	#[method(name = "marketplace_getBuyQuote")]
+	fn buy_quote(
+		&self,
+		nft_id: NFTId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<BuyQuote<AccountId, NumberOrHex>>>;
+}
+
+/// Provides RPC methods to query the marketplace listings.
+pub struct Marketplace<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Marketplace<C, Block> {
+	/// Creates a new instance of the marketplace RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> MarketplaceApiServer<<Block as BlockT>::Hash> for Marketplace<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: MarketplaceRuntimeApi<Block, AccountId, Balance>,
+{
+	fn listings(
+		&self,
+		marketplace_id: MarketplaceId,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Page<NFTId, Listing<AccountId, NumberOrHex>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let listings = self
+			.client
+			.runtime_api()
+			.listings(at, marketplace_id, start_after, limit.unwrap_or(MAX_PAGE_SIZE))
+			.map_err(runtime_error_into_rpc_err)?;
+
+		Ok(listings.map(|listing| listing.map(NumberOrHex::from)))
+	}
+
+	fn buy_quote(
+		&self,
+		nft_id: NFTId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<BuyQuote<AccountId, NumberOrHex>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let quote = self
+			.client
+			.runtime_api()
+			.buy_quote(at, nft_id)
+			.map_err(runtime_error_into_rpc_err)?;
+
+		Ok(quote.map(|quote| quote.map(NumberOrHex::from)))
+	}
+}
@@ -0,0 +1,257 @@
+//! RPC interface for the `ternoa_nft` runtime API.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+use ternoa_core_primitives::{AccountId, Balance};
+use ternoa_runtime_api::{
+	nft::{
+		CollectionDetails, CollectionId, MintFees, NFTId, NftApi as NftRuntimeApi, NftDetails,
+		NftState,
+	},
+	Page, MAX_PAGE_SIZE,
+};
+
+use super::runtime_error_into_rpc_err;
+
+/// NFT RPC methods.
+#[rpc(server)]
+pub trait NftApi<BlockHash> {
+	/// Returns the NFT with the given id.
+	#[method(name = "nft_getNft")]
+	fn nft(&self, nft_id: NFTId, at: Option<BlockHash>)
+		-> RpcResult<Option<NftDetails<AccountId>>>;
+
+	/// Returns the NFTs owned by the given account among at most `limit` NFTs, starting after
+	/// the NFT `start_after`.
+	#[method(name = "nft_getNftsByOwner")]
+	fn nfts_by_owner(
+		&self,
+		owner: AccountId,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Page<NFTId, NftDetails<AccountId>>>;
+
+	/// Returns whether the NFT is listed, rented, delegated, secret, a capsule or in transmission.
+	#[method(name = "nft_getNftState")]
+	fn nft_state(&self, nft_id: NFTId, at: Option<BlockHash>) -> RpcResult<Option<NftState>>;
+
+	/// Returns the collection with the given id.
+	#[method(name = "nft_getCollection")]
+	fn collection(
+		&self,
+		collection_id: CollectionId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<CollectionDetails<AccountId>>>;
+
+	/// Returns all the NFTs of the given collection.
+	#[method(name = "nft_getCollectionNfts")]
+	fn collection_nfts(
+		&self,
+		collection_id: CollectionId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<NftDetails<AccountId>>>;
+
+	/// Returns the current NFT, secret NFT and capsule mint fees.
+	#[method(name = "nft_getMintFees")]
+	fn mint_fees(&self, at: Option<BlockHash>) -> RpcResult<MintFees<NumberOrHex>>;
+}
+
+/// Provides RPC methods to query the NFTs and collections.
+pub struct Nft<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Nft<C, Block> {
+	/// Creates a new instance of the NFT RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> NftApiServer<<Block as BlockT>::Hash> for Nft<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: NftRuntimeApi<Block, AccountId, Balance>,
+{
+	fn nft(
+		&self,
+		nft_id: NFTId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<NftDetails<AccountId>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().nft(at, nft_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn nfts_by_owner(
+		&self,
+		owner: AccountId,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Page<NFTId, NftDetails<AccountId>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.nfts_by_owner(at, owner, start_after, limit.unwrap_or(MAX_PAGE_SIZE))
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn nft_state(&self, nft_id: NFTId, at: Option<Block::Hash>) -> RpcResult<Option<NftState>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.nft_state(at, nft_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn collection(
+		&self,
+		collection_id: CollectionId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<CollectionDetails<AccountId>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.collection(at, collection_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn collection_nfts(
+		&self,
+		collection_id: CollectionId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<NftDetails<AccountId>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.collection_nfts(at, collection_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn mint_fees(&self, at: Option<Block::Hash>) -> RpcResult<MintFees<NumberOrHex>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let fees = self.client.runtime_api().mint_fees(at).map_err(runtime_error_into_rpc_err)?;
+
+		Ok(fees.map(NumberOrHex::from))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use jsonrpsee::{core::traits::ToRpcParams, types::EmptyServerParams};
+	use serde_json::{json, Value};
+	use sp_api::ApiRef;
+	use sp_blockchain::{BlockStatus, Info};
+	use ternoa_core_primitives::{Block, BlockNumber, Hash, Header};
+
+	/// Serves the NFT runtime API with fixed answers.
+	struct TestClient;
+
+	#[derive(Clone)]
+	struct TestApi;
+
+	sp_api::mock_impl_runtime_apis! {
+		impl NftRuntimeApi<Block, AccountId, Balance> for TestApi {
+			fn nft(_nft_id: NFTId) -> Option<NftDetails<AccountId>> {
+				None
+			}
+
+			// The limit is returned as `next`, so that the tests can check its default.
+			fn nfts_by_owner(
+				_owner: AccountId,
+				_start_after: Option<NFTId>,
+				limit: u32,
+			) -> Page<NFTId, NftDetails<AccountId>> {
+				Page { items: Vec::new(), next: Some(limit) }
+			}
+
+			fn nft_state(_nft_id: NFTId) -> Option<NftState> {
+				None
+			}
+
+			fn collection(_collection_id: CollectionId) -> Option<CollectionDetails<AccountId>> {
+				None
+			}
+
+			fn collection_nfts(_collection_id: CollectionId) -> Vec<NftDetails<AccountId>> {
+				Vec::new()
+			}
+
+			fn mint_fees() -> MintFees<Balance> {
+				MintFees { nft: 10u128.pow(20), secret_nft: 50, capsule: 0 }
+			}
+		}
+	}
+
+	impl ProvideRuntimeApi<Block> for TestClient {
+		type Api = TestApi;
+
+		fn runtime_api(&self) -> ApiRef<'_, Self::Api> {
+			TestApi.into()
+		}
+	}
+
+	impl HeaderBackend<Block> for TestClient {
+		fn header(&self, _hash: Hash) -> sp_blockchain::Result<Option<Header>> {
+			Ok(None)
+		}
+
+		fn info(&self) -> Info<Block> {
+			Info {
+				best_hash: Hash::repeat_byte(1),
+				best_number: 0,
+				genesis_hash: Hash::repeat_byte(1),
+				finalized_hash: Hash::repeat_byte(1),
+				finalized_number: 0,
+				finalized_state: None,
+				number_leaves: 1,
+				block_gap: None,
+			}
+		}
+
+		fn status(&self, _hash: Hash) -> sp_blockchain::Result<BlockStatus> {
+			Ok(BlockStatus::Unknown)
+		}
+
+		fn number(&self, _hash: Hash) -> sp_blockchain::Result<Option<BlockNumber>> {
+			Ok(None)
+		}
+
+		fn hash(&self, _number: BlockNumber) -> sp_blockchain::Result<Option<Hash>> {
+			Ok(None)
+		}
+	}
+
+	fn call(method: &str, params: impl ToRpcParams + Send) -> Value {
+		let rpc = Nft::<_, Block>::new(Arc::new(TestClient)).into_rpc();
+		futures::executor::block_on(rpc.call(method, params)).unwrap()
+	}
+
+	#[test]
+	fn defaults_the_page_size() {
+		let alice = json!(sp_keyring::Sr25519Keyring::Alice.to_account_id());
+
+		let page = call("nft_getNftsByOwner", [alice.clone()]);
+		assert_eq!(page, json!({ "items": [], "next": MAX_PAGE_SIZE }));
+
+		let page = call("nft_getNftsByOwner", [alice, json!(4), json!(10)]);
+		assert_eq!(page, json!({ "items": [], "next": 10 }));
+	}
+
+	#[test]
+	fn returns_large_fees_as_hex() {
+		assert_eq!(
+			call("nft_getMintFees", EmptyServerParams::new()),
+			json!({ "nft": "0x56bc75e2d63100000", "secretNft": 50, "capsule": 0 })
+		);
+	}
+}
@@ -0,0 +1,120 @@
+//! RPC interface for the `ternoa_rent` runtime API.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+use ternoa_core_primitives::{AccountId, Balance, BlockNumber};
+use ternoa_runtime_api::{
+	nft::NFTId,
+	rent::{RentApi as RentRuntimeApi, RentContract},
+	Page, MAX_PAGE_SIZE,
+};
+
+use super::runtime_error_into_rpc_err;
+
+/// Rent RPC methods.
+#[rpc(server)]
+pub trait RentApi<BlockHash> {
+	/// Returns the rent contract of the given NFT, with its offers and next subscription payment.
+	#[method(name = "rent_getContract")]
+	fn contract(
+		&self,
+		nft_id: NFTId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<RentContract<AccountId, BlockNumber, NumberOrHex>>>;
+
+	/// Returns the contracts created by the given NFT owner among at most `limit` contracts,
+	/// starting after the contract of the NFT `start_after`.
+	#[method(name = "rent_getContractsByRenter")]
+	fn contracts_by_renter(
+		&self,
+		renter: AccountId,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Page<NFTId, RentContract<AccountId, BlockNumber, NumberOrHex>>>;
+
+	/// Returns the contracts in which the given account is renting the NFT among at most `limit`
+	/// contracts, starting after the contract of the NFT `start_after`.
+	#[method(name = "rent_getContractsByRentee")]
+	fn contracts_by_rentee(
+		&self,
+		rentee: AccountId,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Page<NFTId, RentContract<AccountId, BlockNumber, NumberOrHex>>>;
+}
+
+/// Provides RPC methods to query the rent contracts.
+pub struct Rent<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Rent<C, Block> {
+	/// Creates a new instance of the rent RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> RentApiServer<<Block as BlockT>::Hash> for Rent<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: RentRuntimeApi<Block, AccountId, BlockNumber, Balance>,
+{
+	fn contract(
+		&self,
+		nft_id: NFTId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<RentContract<AccountId, BlockNumber, NumberOrHex>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let contract = self
+			.client
+			.runtime_api()
+			.contract(at, nft_id)
+			.map_err(runtime_error_into_rpc_err)?;
+
+		Ok(contract.map(|contract| contract.map(NumberOrHex::from)))
+	}
+
+	fn contracts_by_renter(
+		&self,
+		renter: AccountId,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Page<NFTId, RentContract<AccountId, BlockNumber, NumberOrHex>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let contracts = self
+			.client
+			.runtime_api()
+			.contracts_by_renter(at, renter, start_after, limit.unwrap_or(MAX_PAGE_SIZE))
+			.map_err(runtime_error_into_rpc_err)?;
+
+		Ok(contracts.map(|contract| contract.map(NumberOrHex::from)))
+	}
+
+	fn contracts_by_rentee(
+		&self,
+		rentee: AccountId,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Page<NFTId, RentContract<AccountId, BlockNumber, NumberOrHex>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let contracts = self
+			.client
+			.runtime_api()
+			.contracts_by_rentee(at, rentee, start_after, limit.unwrap_or(MAX_PAGE_SIZE))
+			.map_err(runtime_error_into_rpc_err)?;
+
+		Ok(contracts.map(|contract| contract.map(NumberOrHex::from)))
+	}
+}
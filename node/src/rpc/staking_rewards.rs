@@ -0,0 +1,83 @@
+//! RPC interface for the `ternoa_staking_rewards` runtime API.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+use ternoa_core_primitives::{Balance, EraIndex};
+use ternoa_runtime_api::staking_rewards::{
+	EraRewardEstimate, StakingRewardsApi as StakingRewardsRuntimeApi,
+};
+
+use super::runtime_error_into_rpc_err;
+
+/// Staking rewards RPC methods.
+#[rpc(server)]
+pub trait StakingRewardsApi<BlockHash> {
+	/// Returns the payout of the active era as if it ended at the given block.
+	#[method(name = "stakingRewards_getActiveEraEstimate")]
+	fn active_era_reward_estimate(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<EraRewardEstimate<NumberOrHex>>>;
+
+	/// Returns the amount paid to the validators of the given era.
+	#[method(name = "stakingRewards_getEraValidatorReward")]
+	fn era_validator_reward(
+		&self,
+		era: EraIndex,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<NumberOrHex>>;
+}
+
+/// Provides RPC methods to query the era rewards.
+pub struct StakingRewards<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> StakingRewards<C, Block> {
+	/// Creates a new instance of the staking rewards RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> StakingRewardsApiServer<<Block as BlockT>::Hash> for StakingRewards<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: StakingRewardsRuntimeApi<Block, Balance>,
+{
+	fn active_era_reward_estimate(
+		&self,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<EraRewardEstimate<NumberOrHex>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let estimate = self
+			.client
+			.runtime_api()
+			.active_era_reward_estimate(at)
+			.map_err(runtime_error_into_rpc_err)?;
+
+		Ok(estimate.map(|estimate| estimate.map(NumberOrHex::from)))
+	}
+
+	fn era_validator_reward(
+		&self,
+		era: EraIndex,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<NumberOrHex>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let reward = self
+			.client
+			.runtime_api()
+			.era_validator_reward(at, era)
+			.map_err(runtime_error_into_rpc_err)?;
+
+		Ok(reward.map(NumberOrHex::from))
+	}
+}
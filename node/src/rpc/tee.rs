@@ -0,0 +1,207 @@
+//! RPC interface for the `ternoa_tee` runtime API.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+use ternoa_core_primitives::{AccountId, Balance};
+use ternoa_runtime_api::{
+	nft::NFTId,
+	tee::{
+		ClusterId, ClusterInfo, EnclaveInfo, MetricsReport, PendingRequests, RewardsEstimate,
+		ShardHolder, TeeApi as TeeRuntimeApi,
+	},
+};
+
+use super::runtime_error_into_rpc_err;
+
+/// TEE RPC methods.
+#[rpc(server)]
+pub trait TeeApi<BlockHash> {
+	/// Returns all the clusters.
+	#[method(name = "tee_getClusters")]
+	fn clusters(&self, at: Option<BlockHash>) -> RpcResult<Vec<ClusterInfo<AccountId>>>;
+
+	/// Returns the cluster with the given id.
+	#[method(name = "tee_getCluster")]
+	fn cluster(
+		&self,
+		cluster_id: ClusterId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<ClusterInfo<AccountId>>>;
+
+	/// Returns the enclave registered by the given operator.
+	#[method(name = "tee_getEnclave")]
+	fn enclave(
+		&self,
+		operator: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<EnclaveInfo<AccountId>>>;
+
+	/// Returns the operator of the enclave signing with the given address.
+	#[method(name = "tee_getEnclaveOperator")]
+	fn enclave_operator(
+		&self,
+		enclave_address: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<AccountId>>;
+
+	/// Returns the enclave registrations, updates and unregistrations awaiting approval.
+	#[method(name = "tee_getPendingRequests")]
+	fn pending_requests(&self, at: Option<BlockHash>) -> RpcResult<PendingRequests<AccountId>>;
+
+	/// Returns the metrics reports of the given operator for the current and previous eras.
+	#[method(name = "tee_getLatestMetricsReports")]
+	fn latest_metrics_reports(
+		&self,
+		operator: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<MetricsReport<AccountId>>>;
+
+	/// Returns an estimate of the rewards the given operator can claim.
+	#[method(name = "tee_getClaimableRewards")]
+	fn claimable_rewards(
+		&self,
+		operator: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<RewardsEstimate<NumberOrHex>>;
+
+	/// Returns the enclaves holding a shard of the given secret NFT.
+	#[method(name = "tee_getSecretNftShardHolders")]
+	fn secret_nft_shard_holders(
+		&self,
+		nft_id: NFTId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<ShardHolder<AccountId>>>;
+
+	/// Returns the enclaves holding a shard of the given capsule.
+	#[method(name = "tee_getCapsuleShardHolders")]
+	fn capsule_shard_holders(
+		&self,
+		nft_id: NFTId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<ShardHolder<AccountId>>>;
+}
+
+/// Provides RPC methods to query the TEE clusters and enclaves.
+pub struct Tee<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Tee<C, Block> {
+	/// Creates a new instance of the TEE RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> TeeApiServer<<Block as BlockT>::Hash> for Tee<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: TeeRuntimeApi<Block, AccountId, Balance>,
+{
+	fn clusters(&self, at: Option<Block::Hash>) -> RpcResult<Vec<ClusterInfo<AccountId>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().clusters(at).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn cluster(
+		&self,
+		cluster_id: ClusterId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<ClusterInfo<AccountId>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.cluster(at, cluster_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn enclave(
+		&self,
+		operator: AccountId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<EnclaveInfo<AccountId>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.enclave(at, operator)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn enclave_operator(
+		&self,
+		enclave_address: AccountId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<AccountId>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.enclave_operator(at, enclave_address)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn pending_requests(&self, at: Option<Block::Hash>) -> RpcResult<PendingRequests<AccountId>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.pending_requests(at)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn latest_metrics_reports(
+		&self,
+		operator: AccountId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<MetricsReport<AccountId>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.latest_metrics_reports(at, operator)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn claimable_rewards(
+		&self,
+		operator: AccountId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<RewardsEstimate<NumberOrHex>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let estimate = self
+			.client
+			.runtime_api()
+			.claimable_rewards(at, operator)
+			.map_err(runtime_error_into_rpc_err)?;
+
+		Ok(estimate.map(NumberOrHex::from))
+	}
+
+	fn secret_nft_shard_holders(
+		&self,
+		nft_id: NFTId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<ShardHolder<AccountId>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.secret_nft_shard_holders(at, nft_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn capsule_shard_holders(
+		&self,
+		nft_id: NFTId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<ShardHolder<AccountId>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.capsule_shard_holders(at, nft_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+}
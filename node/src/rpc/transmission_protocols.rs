@@ -0,0 +1,88 @@
+//! RPC interface for the `ternoa_transmission_protocols` runtime API.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use ternoa_core_primitives::{AccountId, BlockNumber};
+use ternoa_runtime_api::{
+	nft::NFTId,
+	transmission_protocols::{
+		PendingTransmission, TransmissionProtocolsApi as TransmissionProtocolsRuntimeApi,
+	},
+	Page, MAX_PAGE_SIZE,
+};
+
+use super::runtime_error_into_rpc_err;
+
+/// Transmission protocols RPC methods.
+#[rpc(server)]
+pub trait TransmissionProtocolsApi<BlockHash> {
+	/// Returns the pending transmission of the given NFT.
+	#[method(name = "transmissionProtocols_getTransmission")]
+	fn transmission(
+		&self,
+		nft_id: NFTId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<PendingTransmission<AccountId, BlockNumber>>>;
+
+	/// Returns the pending transmissions that will send an NFT to the given account among at
+	/// most `limit` transmissions, starting after the transmission of the NFT `start_after`.
+	#[method(name = "transmissionProtocols_getTransmissionsByRecipient")]
+	fn transmissions_by_recipient(
+		&self,
+		recipient: AccountId,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Page<NFTId, PendingTransmission<AccountId, BlockNumber>>>;
+}
+
+/// Provides RPC methods to query the pending transmissions.
+pub struct TransmissionProtocols<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> TransmissionProtocols<C, Block> {
+	/// Creates a new instance of the transmission protocols RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> TransmissionProtocolsApiServer<<Block as BlockT>::Hash>
+	for TransmissionProtocols<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: TransmissionProtocolsRuntimeApi<Block, AccountId, BlockNumber>,
+{
+	fn transmission(
+		&self,
+		nft_id: NFTId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<PendingTransmission<AccountId, BlockNumber>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.transmission(at, nft_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn transmissions_by_recipient(
+		&self,
+		recipient: AccountId,
+		start_after: Option<NFTId>,
+		limit: Option<u32>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Page<NFTId, PendingTransmission<AccountId, BlockNumber>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.transmissions_by_recipient(at, recipient, start_after, limit.unwrap_or(MAX_PAGE_SIZE))
+			.map_err(runtime_error_into_rpc_err)
+	}
+}
@@ -1,96 +1,310 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 
-use std::{sync::Arc};
+use std::{pin::Pin, sync::Arc, time::Duration};
 
-use sp_runtime::traits::Block as BlockT;
+use futures::{channel::mpsc, prelude::*};
+
+use sp_api::ConstructRuntimeApi;
+use sp_runtime::traits::{BlakeTwo256, Block as BlockT};
+use sp_trie::PrefixedMemoryDB;
 
 use sc_client_api::BlockBackend;
 use sc_consensus_babe::SlotProportion;
+use sc_consensus_manual_seal::{
+	consensus::{babe::BabeConsensusDataProvider, timestamp::SlotTimestampProvider},
+	EngineCommand, ManualSealParams,
+};
+use sc_executor::NativeExecutionDispatch;
 use sc_network::NetworkService;
 use sc_network_common::sync::warp::WarpSyncParams;
 use sc_network_sync::SyncingService;
 use sc_rpc_api::DenyUnsafe;
-use sc_service::{error::Error as ServiceError, Configuration, TaskManager, RpcHandlers};
-use sc_telemetry::{Telemetry, TelemetryWorker};
+use sc_service::{
+	error::Error as ServiceError, ChainSpec, ChainType, Configuration, RpcHandlers, TaskManager,
+};
+use sc_telemetry::{Telemetry, TelemetryHandle, TelemetryWorker};
+use sc_transaction_pool_api::TransactionPool as _;
+use sp_keystore::KeystorePtr;
+use substrate_prometheus_endpoint::Registry;
 pub use sc_executor::NativeElseWasmExecutor;
 
 use frame_benchmarking_cli::SUBSTRATE_REFERENCE_HARDWARE;
 
-use alphanet_runtime::RuntimeApi;
-use ternoa_core_primitives::Block;
+pub use ternoa_client::{Client, FullBackend, FullClient, RuntimeApiCollection};
+use ternoa_core_primitives::{Block, BlockNumber, Hash};
+
+#[cfg(feature = "alphanet-native")]
+pub use alphanet_runtime;
+#[cfg(feature = "alphanet-native")]
+pub use ternoa_client::AlphanetExecutorDispatch;
 
-use crate::cli::Cli;
+#[cfg(feature = "mainnet-native")]
+pub use mainnet_runtime;
+#[cfg(feature = "mainnet-native")]
+pub use ternoa_client::MainnetExecutorDispatch;
+
+use crate::chain_spec::{ConsensusParams, Extensions, VotingRule};
+use crate::cli::Sealing;
+use crate::metrics::{self, Metrics};
 use crate::rpc::{FullDeps, BabeDeps, GrandpaDeps, create_full};
-// Our native executor instance.
-pub struct ExecutorDispatch;
-
-impl sc_executor::NativeExecutionDispatch for ExecutorDispatch {
-	/// Only enable the benchmarking host functions when we actually want to benchmark.
-	#[cfg(feature = "runtime-benchmarks")]
-	type ExtendHostFunctions = frame_benchmarking::benchmarking::HostFunctions;
-	/// Otherwise we only use the default Substrate host functions.
-	#[cfg(not(feature = "runtime-benchmarks"))]
-	type ExtendHostFunctions = ();
-
-	fn dispatch(method: &str, data: &[u8]) -> Option<Vec<u8>> {
-		alphanet_runtime::api::dispatch(method, data)
+
+type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
+/// The NFT index shared by the RPC and the indexing task.
+#[cfg(feature = "indexer")]
+type NftIndex = Arc<crate::indexer::Indexer>;
+#[cfg(not(feature = "indexer"))]
+type NftIndex = ();
+/// The transaction pool type defintion.
+pub type TransactionPool<RuntimeApi, ExecutorDispatch> =
+	sc_transaction_pool::FullPool<Block, FullClient<RuntimeApi, ExecutorDispatch>>;
+type FullGrandpaBlockImport<RuntimeApi, ExecutorDispatch> =
+	sc_consensus_grandpa::GrandpaBlockImport<
+		FullBackend,
+		Block,
+		FullClient<RuntimeApi, ExecutorDispatch>,
+		FullSelectChain,
+	>;
+type FullBabeBlockImport<RuntimeApi, ExecutorDispatch> = sc_consensus_babe::BabeBlockImport<
+	Block,
+	FullClient<RuntimeApi, ExecutorDispatch>,
+	FullGrandpaBlockImport<RuntimeApi, ExecutorDispatch>,
+>;
+/// The sink used by the `engine_*` RPC methods to request blocks when sealing manually.
+pub type CommandSink = mpsc::Sender<EngineCommand<Hash>>;
+
+/// Can be called for a chain spec to check which network it belongs to.
+pub trait IdentifyVariant {
+	/// Returns `true` if this is a chain spec of the Alphanet network.
+	fn is_alphanet(&self) -> bool;
+
+	/// Returns `true` if this is a chain spec of the Mainnet network.
+	fn is_mainnet(&self) -> bool;
+
+	/// Returns `true` if this is a chain spec of a development network.
+	fn is_dev(&self) -> bool;
+}
+
+impl IdentifyVariant for Box<dyn ChainSpec> {
+	fn is_alphanet(&self) -> bool {
+		self.id().starts_with("alphanet")
+	}
+
+	fn is_mainnet(&self) -> bool {
+		self.id().starts_with("mainnet")
 	}
 
-	fn native_version() -> sc_executor::NativeVersion {
-		alphanet_runtime::native_version()
+	fn is_dev(&self) -> bool {
+		self.id().ends_with("dev")
 	}
 }
 
-pub type FullClient =
-	sc_service::TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<ExecutorDispatch>>;
-type FullBackend = sc_service::TFullBackend<Block>;
-type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
-/// The transaction pool type defintion.
-pub type TransactionPool = sc_transaction_pool::FullPool<Block, FullClient>;
-type FullGrandpaBlockImport =
-	sc_consensus_grandpa::GrandpaBlockImport<FullBackend, Block, FullClient, FullSelectChain>;
-
-/// Builds a new service for a full client.
-pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceError> {
-	let database_source = config.database.clone();
-	let task_manager = new_full_base(config, cli.no_hardware_benchmarks, |_, _| ())
-		.map(|NewFullBase { task_manager, .. }| task_manager)?;
-
-	sc_storage_monitor::StorageMonitorService::try_spawn(
-		cli.storage_monitor,
-		database_source,
-		&task_manager.spawn_essential_handle(),
-	)
-	.map_err(|e| ServiceError::Application(e.into()))?;
+/// Builds a new service for a full client, running the runtime selected by the chain spec.
+pub fn build_full(
+	config: Configuration,
+	disable_hardware_benchmarks: bool,
+	sealing: Option<Sealing>,
+) -> Result<NewFull<Arc<Client>>, ServiceError> {
+	#[cfg(feature = "alphanet-native")]
+	if config.chain_spec.is_alphanet() {
+		return new_full_base::<alphanet_runtime::RuntimeApi, AlphanetExecutorDispatch>(
+			config,
+			disable_hardware_benchmarks,
+			sealing,
+			|_, _| (),
+		)
+		.map(|full| full.with_client(Client::Alphanet))
+	}
+
+	#[cfg(feature = "mainnet-native")]
+	if config.chain_spec.is_mainnet() {
+		return new_full_base::<mainnet_runtime::RuntimeApi, MainnetExecutorDispatch>(
+			config,
+			disable_hardware_benchmarks,
+			sealing,
+			|_, _| (),
+		)
+		.map(|full| full.with_client(Client::Mainnet))
+	}
+
+	Err(unknown_chain(&*config.chain_spec))
+}
 
-	Ok(task_manager)
+/// The error returned when the chain spec selects no runtime that this node was built with.
+fn unknown_chain(chain_spec: &dyn ChainSpec) -> ServiceError {
+	ServiceError::Other(format!("No runtime is available for the chain `{}`", chain_spec.id()))
 }
 
 /// Result of [`new_full_base`].
-pub struct NewFullBase {
+pub struct NewFull<C> {
 	/// The task manager of the node.
 	pub task_manager: TaskManager,
 	/// The client instance of the node.
-	pub client: Arc<FullClient>,
+	pub client: C,
 	/// The networking service of the node.
 	pub network: Arc<NetworkService<Block, <Block as BlockT>::Hash>>,
 	/// The syncing service of the node.
 	pub sync: Arc<SyncingService<Block>>,
-	/// The transaction pool of the node.
-	pub transaction_pool: Arc<TransactionPool>,
 	/// The rpc handlers of the node.
 	pub rpc_handlers: RpcHandlers,
 }
 
+impl<C> NewFull<C> {
+	/// Wraps the runtime specific client into a [`Client`].
+	pub fn with_client(self, func: impl FnOnce(C) -> Client) -> NewFull<Arc<Client>> {
+		NewFull {
+			task_manager: self.task_manager,
+			client: Arc::new(func(self.client)),
+			network: self.network,
+			sync: self.sync,
+			rpc_handlers: self.rpc_handlers,
+		}
+	}
+}
+
+/// Builds the components needed by the chain operations (import, export, check, revert...)
+/// for the runtime selected by the chain spec.
+#[allow(clippy::type_complexity)]
+pub fn new_chain_ops(
+	config: &Configuration,
+) -> Result<
+	(
+		Arc<Client>,
+		Arc<FullBackend>,
+		sc_consensus::BasicQueue<Block, PrefixedMemoryDB<BlakeTwo256>>,
+		TaskManager,
+	),
+	ServiceError,
+> {
+	macro_rules! chain_ops {
+		($runtime:ident, $executor:ident, $variant:ident) => {{
+			let sc_service::PartialComponents { client, backend, import_queue, task_manager, .. } =
+				new_partial::<$runtime::RuntimeApi, $executor>(config, None)?;
+			Ok((Arc::new(Client::$variant(client)), backend, import_queue, task_manager))
+		}};
+	}
+
+	#[cfg(feature = "alphanet-native")]
+	if config.chain_spec.is_alphanet() {
+		return chain_ops!(alphanet_runtime, AlphanetExecutorDispatch, Alphanet)
+	}
+
+	#[cfg(feature = "mainnet-native")]
+	if config.chain_spec.is_mainnet() {
+		return chain_ops!(mainnet_runtime, MainnetExecutorDispatch, Mainnet)
+	}
+
+	Err(unknown_chain(&*config.chain_spec))
+}
+
+/// The future returned by [`new_sync_spec`], resolving to the JSON encoded chain spec.
+pub type SyncSpecFuture = Pin<Box<dyn Future<Output = Result<String, ServiceError>> + Send>>;
+
+/// Builds a chain spec checkpointed at the latest finalized block of the local database, for
+/// the runtime selected by the chain spec.
+///
+/// The spec is generated by the `sync_state_genSyncSpec` RPC method, which reads the BABE
+/// epoch changes from the BABE worker. The returned [`TaskManager`] keeps it running.
+pub fn new_sync_spec(
+	config: &Configuration,
+	raw: bool,
+) -> Result<(SyncSpecFuture, TaskManager), ServiceError> {
+	#[cfg(feature = "alphanet-native")]
+	if config.chain_spec.is_alphanet() {
+		return sync_spec::<alphanet_runtime::RuntimeApi, AlphanetExecutorDispatch>(config, raw)
+	}
+
+	#[cfg(feature = "mainnet-native")]
+	if config.chain_spec.is_mainnet() {
+		return sync_spec::<mainnet_runtime::RuntimeApi, MainnetExecutorDispatch>(config, raw)
+	}
+
+	Err(unknown_chain(&*config.chain_spec))
+}
+
+fn sync_spec<RuntimeApi, ExecutorDispatch>(
+	config: &Configuration,
+	raw: bool,
+) -> Result<(SyncSpecFuture, TaskManager), ServiceError>
+where
+	RuntimeApi: ConstructRuntimeApi<Block, FullClient<RuntimeApi, ExecutorDispatch>>
+		+ Send
+		+ Sync
+		+ 'static,
+	RuntimeApi::RuntimeApi:
+		RuntimeApiCollection<StateBackend = sc_client_api::StateBackendFor<FullBackend, Block>>,
+	ExecutorDispatch: NativeExecutionDispatch + 'static,
+{
+	let sc_service::PartialComponents { task_manager, other: (rpc_builder, ..), .. } =
+		new_partial::<RuntimeApi, ExecutorDispatch>(config, None)?;
+	let rpc = rpc_builder(DenyUnsafe::No, Arc::new(task_manager.spawn_handle()))?;
+
+	let sync_spec = async move {
+		let sync_spec = rpc
+			.call::<_, serde_json::Value>("sync_state_genSyncSpec", [raw])
+			.await
+			.map_err(|e| ServiceError::Other(e.to_string()))?;
+
+		serde_json::to_string_pretty(&sync_spec).map_err(|e| ServiceError::Other(e.to_string()))
+	};
+
+	Ok((Box::pin(sync_spec), task_manager))
+}
+
+/// Reverts the last `blocks` blocks, including the BABE and GRANDPA auxiliary data.
+pub fn revert_backend(
+	client: Arc<Client>,
+	backend: Arc<FullBackend>,
+	blocks: BlockNumber,
+) -> Result<(), ServiceError> {
+	match client.as_ref() {
+		#[cfg(feature = "alphanet-native")]
+		Client::Alphanet(client) => {
+			sc_consensus_babe::revert(client.clone(), backend, blocks)?;
+			sc_consensus_grandpa::revert(client.clone(), blocks)?;
+		},
+		#[cfg(feature = "mainnet-native")]
+		Client::Mainnet(client) => {
+			sc_consensus_babe::revert(client.clone(), backend, blocks)?;
+			sc_consensus_grandpa::revert(client.clone(), blocks)?;
+		},
+	}
+
+	Ok(())
+}
+
 /// Creates a full service from the configuration.
-pub fn new_full_base(
+pub fn new_full_base<RuntimeApi, ExecutorDispatch>(
 	mut config: Configuration,
 	disable_hardware_benchmarks: bool,
+	sealing: Option<Sealing>,
 	with_startup_data: impl FnOnce(
-		&sc_consensus_babe::BabeBlockImport<Block, FullClient, FullGrandpaBlockImport>,
+		&FullBabeBlockImport<RuntimeApi, ExecutorDispatch>,
 		&sc_consensus_babe::BabeLink<Block>,
 	),
-) -> Result<NewFullBase, ServiceError> {
+) -> Result<NewFull<Arc<FullClient<RuntimeApi, ExecutorDispatch>>>, ServiceError>
+where
+	RuntimeApi: ConstructRuntimeApi<Block, FullClient<RuntimeApi, ExecutorDispatch>>
+		+ Send
+		+ Sync
+		+ 'static,
+	RuntimeApi::RuntimeApi:
+		RuntimeApiCollection<StateBackend = sc_client_api::StateBackendFor<FullBackend, Block>>,
+	ExecutorDispatch: NativeExecutionDispatch + 'static,
+{
+	if sealing.is_some() && config.chain_spec.chain_type() != ChainType::Development {
+		return Err(ServiceError::Other(
+			"`--sealing` can only be used with development chains".into(),
+		))
+	}
+
+	let (command_sink, commands_stream) = match sealing {
+		Some(_) => {
+			let (sink, stream) = mpsc::channel(1024);
+			(Some(sink), Some(stream))
+		},
+		None => (None, None),
+	};
+
 	let hwbench = (!disable_hardware_benchmarks)
 		.then_some(config.database.path().map(|database_path| {
 			let _ = std::fs::create_dir_all(&database_path);
@@ -106,19 +320,23 @@ pub fn new_full_base(
 		keystore_container,
 		select_chain,
 		transaction_pool,
-		other: (rpc_builder, import_setup, rpc_setup, mut telemetry),
-	} = new_partial(&config)?;
+		other: (rpc_builder, import_setup, rpc_setup, mut telemetry, statement_store, indexer),
+	} = new_partial::<RuntimeApi, ExecutorDispatch>(&config, command_sink)?;
 
 	let shared_voter_state = rpc_setup;
-	let grandpa_protocol_name = sc_consensus_grandpa::protocol_standard_name(
-		&client.block_hash(0).ok().flatten().expect("Genesis block exists; qed"),
-		&config.chain_spec,
-	);
+	let genesis_hash = client.block_hash(0).ok().flatten().expect("Genesis block exists; qed");
+	let grandpa_protocol_name =
+		sc_consensus_grandpa::protocol_standard_name(&genesis_hash, &config.chain_spec);
 
 	config
 		.network
 		.extra_sets
 		.push(sc_consensus_grandpa::grandpa_peers_set_config(grandpa_protocol_name.clone()));
+	let statement_handler_proto = sc_network_statement::StatementHandlerPrototype::new(
+		genesis_hash,
+		config.chain_spec.fork_id(),
+	);
+	config.network.extra_sets.push(statement_handler_proto.set_config());
 	let warp_sync = Arc::new(sc_consensus_grandpa::warp_proof::NetworkProvider::new(
 		backend.clone(),
 		import_setup.1.shared_authority_set().clone(),
@@ -136,6 +354,26 @@ pub fn new_full_base(
 			warp_sync_params: Some(WarpSyncParams::WithProvider(warp_sync)),
 		})?;
 
+	// Gossips the statements of the TEE enclaves and NFT owners, see `ValidateStatement`.
+	let statement_protocol_executor = {
+		let spawn_handle = task_manager.spawn_handle();
+		Box::new(move |fut| {
+			spawn_handle.spawn("network-statement-validator", Some("networking"), fut);
+		})
+	};
+	let statement_handler = statement_handler_proto.build(
+		network.clone(),
+		sync_service.clone(),
+		statement_store,
+		config.prometheus_registry(),
+		statement_protocol_executor,
+	)?;
+	task_manager.spawn_handle().spawn(
+		"network-statement-handler",
+		Some("networking"),
+		statement_handler.run(),
+	);
+
 	if config.offchain_worker.enabled {
 		sc_service::build_offchain_workers(
 			&config,
@@ -151,7 +389,15 @@ pub fn new_full_base(
 		Some(sc_consensus_slots::BackoffAuthoringOnFinalizedHeadLagging::default());
 	let name = config.network.node_name.clone();
 	let enable_grandpa = !config.disable_grandpa;
+	#[cfg(feature = "indexer")]
+	let is_archive = matches!(
+		config.state_pruning,
+		Some(sc_service::PruningMode::ArchiveAll | sc_service::PruningMode::ArchiveCanonical)
+	);
 	let prometheus_registry = config.prometheus_registry().cloned();
+	let consensus = Extensions::try_get(&*config.chain_spec)
+		.map(|extensions| extensions.consensus.clone())
+		.unwrap_or_default();
 
 	let rpc_handlers = sc_service::spawn_tasks(sc_service::SpawnTasksParams {
 		config,
@@ -168,6 +414,29 @@ pub fn new_full_base(
 		telemetry: telemetry.as_mut(),
 	})?;
 
+	if let Some(registry) = prometheus_registry.as_ref() {
+		let metrics = Metrics::register(registry)?;
+		task_manager.spawn_handle().spawn(
+			"ternoa-metrics",
+			None,
+			metrics::run(client.clone(), sync_service.clone(), metrics),
+		);
+	}
+
+	// The index reads the events from the state of every block, which a pruned node drops.
+	#[cfg(feature = "indexer")]
+	if is_archive {
+		task_manager.spawn_handle().spawn_blocking(
+			"ternoa-indexer",
+			None,
+			crate::indexer::run(client.clone(), indexer),
+		);
+	} else {
+		log::warn!("⚠️  The NFT index only runs with `--state-pruning archive`, it is disabled.");
+	}
+	#[cfg(not(feature = "indexer"))]
+	let () = indexer;
+
 	if let Some(hwbench) = hwbench {
 		sc_sysinfo::print_hwbench(&hwbench);
 		if !SUBSTRATE_REFERENCE_HARDWARE.check_hardware(&hwbench) && role.is_authority() {
@@ -190,7 +459,21 @@ pub fn new_full_base(
 
 	(with_startup_data)(&block_import, &babe_link);
 
-	if let sc_service::config::Role::Authority { .. } = &role {
+	if let (Some(sealing), Some(commands_stream)) = (sealing, commands_stream) {
+		start_manual_seal(
+			sealing,
+			&task_manager,
+			client.clone(),
+			transaction_pool.clone(),
+			select_chain,
+			block_import,
+			&babe_link,
+			keystore_container.keystore(),
+			commands_stream,
+			prometheus_registry.as_ref(),
+			telemetry.as_ref().map(|x| x.handle()),
+		)?;
+	} else if let sc_service::config::Role::Authority { .. } = &role {
 		let proposer = sc_basic_authorship::ProposerFactory::new(
 			task_manager.spawn_handle(),
 			client.clone(),
@@ -232,8 +515,12 @@ pub fn new_full_base(
 			force_authoring,
 			backoff_authoring_blocks,
 			babe_link,
-			block_proposal_slot_portion: SlotProportion::new(0.5),
-			max_block_proposal_slot_portion: None,
+			block_proposal_slot_portion: SlotProportion::new(
+				consensus.babe_block_proposal_slot_portion,
+			),
+			max_block_proposal_slot_portion: consensus
+				.babe_max_block_proposal_slot_portion
+				.map(SlotProportion::new),
 			telemetry: telemetry.as_ref().map(|x| x.handle()),
 		};
 
@@ -251,9 +538,8 @@ pub fn new_full_base(
 		if role.is_authority() { Some(keystore_container.keystore()) } else { None };
 
 	let config = sc_consensus_grandpa::Config {
-		// FIXME #1578 make this available through chainspec
-		gossip_duration: std::time::Duration::from_millis(333),
-		justification_period: 512,
+		gossip_duration: Duration::from_millis(consensus.grandpa_gossip_duration_millis),
+		justification_period: consensus.grandpa_justification_period.get(),
 		name: Some(name),
 		observer_enabled: false,
 		keystore,
@@ -262,7 +548,8 @@ pub fn new_full_base(
 		protocol_name: grandpa_protocol_name,
 	};
 
-	if enable_grandpa {
+	// Blocks sealed on demand are finalized by the sealing task or through `engine_finalizeBlock`.
+	if enable_grandpa && sealing.is_none() {
 		// start the full GRANDPA voter
 		// NOTE: non-authorities could run the GRANDPA observer protocol, but at
 		// this point the full voter should provide better guarantees of block
@@ -275,7 +562,7 @@ pub fn new_full_base(
 			network: network.clone(),
 			sync: Arc::new(sync_service.clone()),
 			telemetry: telemetry.as_ref().map(|x| x.handle()),
-			voting_rule: sc_consensus_grandpa::VotingRulesBuilder::default().build(),
+			voting_rule: grandpa_voting_rule(&consensus),
 			prometheus_registry,
 			shared_voter_state,
 		};
@@ -290,42 +577,180 @@ pub fn new_full_base(
 	}
 
 	network_starter.start_network();
-	Ok(NewFullBase {
-		task_manager,
-		client,
-		network,
-		sync: sync_service,
-		transaction_pool,
-		rpc_handlers,
-	})
+	Ok(NewFull { task_manager, client, network, sync: sync_service, rpc_handlers })
+}
+
+/// Builds the GRANDPA voting rule set in the chain spec, or the default one.
+fn grandpa_voting_rule<B>(
+	consensus: &ConsensusParams,
+) -> impl sc_consensus_grandpa::VotingRule<Block, B> + Clone
+where
+	B: sp_blockchain::HeaderBackend<Block> + 'static,
+{
+	use sc_consensus_grandpa::{
+		BeforeBestBlockBy, ThreeQuartersOfTheUnfinalizedChain, VotingRulesBuilder,
+	};
+
+	let Some(rules) = &consensus.grandpa_voting_rules else {
+		return VotingRulesBuilder::default().build()
+	};
+
+	rules
+		.iter()
+		.fold(VotingRulesBuilder::new(), |builder, rule| match rule {
+			VotingRule::BeforeBestBlockBy(blocks) => builder.add(BeforeBestBlockBy(*blocks)),
+			VotingRule::ThreeQuartersOfTheUnfinalizedChain =>
+				builder.add(ThreeQuartersOfTheUnfinalizedChain),
+		})
+		.build()
+}
+
+/// Seals blocks on demand with BABE-compatible digests, in place of BABE and GRANDPA.
+///
+/// Blocks requested through the `engine_*` RPC methods are always sealed. Depending on
+/// `sealing`, blocks are also sealed when transactions enter the pool or at a fixed interval.
+#[allow(clippy::too_many_arguments)]
+fn start_manual_seal<RuntimeApi, ExecutorDispatch>(
+	sealing: Sealing,
+	task_manager: &TaskManager,
+	client: Arc<FullClient<RuntimeApi, ExecutorDispatch>>,
+	transaction_pool: Arc<TransactionPool<RuntimeApi, ExecutorDispatch>>,
+	select_chain: FullSelectChain,
+	block_import: FullBabeBlockImport<RuntimeApi, ExecutorDispatch>,
+	babe_link: &sc_consensus_babe::BabeLink<Block>,
+	keystore: KeystorePtr,
+	rpc_commands: mpsc::Receiver<EngineCommand<Hash>>,
+	prometheus_registry: Option<&Registry>,
+	telemetry: Option<TelemetryHandle>,
+) -> Result<(), ServiceError>
+where
+	RuntimeApi: ConstructRuntimeApi<Block, FullClient<RuntimeApi, ExecutorDispatch>>
+		+ Send
+		+ Sync
+		+ 'static,
+	RuntimeApi::RuntimeApi:
+		RuntimeApiCollection<StateBackend = sc_client_api::StateBackendFor<FullBackend, Block>>,
+	ExecutorDispatch: NativeExecutionDispatch + 'static,
+{
+	let proposer = sc_basic_authorship::ProposerFactory::new(
+		task_manager.spawn_handle(),
+		client.clone(),
+		transaction_pool.clone(),
+		prometheus_registry,
+		telemetry,
+	);
+
+	let consensus_data_provider = BabeConsensusDataProvider::new(
+		client.clone(),
+		keystore,
+		babe_link.epoch_changes().clone(),
+		babe_link.config().authorities.clone(),
+	)
+	.map_err(|e| ServiceError::Other(e.to_string()))?;
+
+	let seal_new_block = |create_empty| EngineCommand::SealNewBlock {
+		create_empty,
+		finalize: true,
+		parent_hash: None,
+		sender: None,
+	};
+	let commands_stream: Pin<Box<dyn Stream<Item = EngineCommand<Hash>> + Send>> = match sealing {
+		Sealing::Manual => Box::pin(rpc_commands),
+		Sealing::Instant => Box::pin(stream::select(
+			rpc_commands,
+			transaction_pool.import_notification_stream().map(move |_| seal_new_block(false)),
+		)),
+		Sealing::Interval(millis) => Box::pin(stream::select(
+			rpc_commands,
+			stream::unfold((), move |()| async move {
+				futures_timer::Delay::new(Duration::from_millis(millis)).await;
+				Some((seal_new_block(true), ()))
+			}),
+		)),
+	};
+
+	let client_clone = client.clone();
+	let manual_seal = sc_consensus_manual_seal::run_manual_seal(ManualSealParams {
+		block_import,
+		env: proposer,
+		client: client.clone(),
+		pool: transaction_pool,
+		commands_stream,
+		select_chain,
+		consensus_data_provider: Some(Box::new(consensus_data_provider)),
+		create_inherent_data_providers: move |parent, ()| {
+			let client_clone = client_clone.clone();
+			async move {
+				// Moves the timestamp forward by one slot per block so that BABE accepts it.
+				let timestamp = SlotTimestampProvider::new_babe(client_clone.clone())?;
+				let slot =
+					sp_consensus_babe::inherents::InherentDataProvider::new(timestamp.slot());
+
+				let storage_proof =
+					sp_transaction_storage_proof::registration::new_data_provider(
+						&*client_clone,
+						&parent,
+					)?;
+
+				Ok((slot, timestamp, storage_proof))
+			}
+		},
+	});
+
+	task_manager.spawn_essential_handle().spawn_blocking(
+		"manual-seal",
+		Some("block-authoring"),
+		manual_seal,
+	);
+
+	Ok(())
 }
 
 /// Creates a new partial node.
-pub fn new_partial(
+///
+/// `command_sink` exposes the `engine_*` RPC methods when blocks are sealed on demand.
+#[allow(clippy::type_complexity)]
+pub fn new_partial<RuntimeApi, ExecutorDispatch>(
 	config: &Configuration,
+	command_sink: Option<CommandSink>,
 ) -> Result<
 	sc_service::PartialComponents<
-		FullClient,
+		FullClient<RuntimeApi, ExecutorDispatch>,
 		FullBackend,
 		FullSelectChain,
-		sc_consensus::DefaultImportQueue<Block, FullClient>,
-		sc_transaction_pool::FullPool<Block, FullClient>,
+		sc_consensus::DefaultImportQueue<Block, FullClient<RuntimeApi, ExecutorDispatch>>,
+		TransactionPool<RuntimeApi, ExecutorDispatch>,
 		(
 			impl Fn(
 				DenyUnsafe,
 				sc_rpc::SubscriptionTaskExecutor,
 			) -> Result<jsonrpsee::RpcModule<()>, sc_service::Error>,
 			(
-				sc_consensus_babe::BabeBlockImport<Block, FullClient, FullGrandpaBlockImport>,
-				sc_consensus_grandpa::LinkHalf<Block, FullClient, FullSelectChain>,
+				FullBabeBlockImport<RuntimeApi, ExecutorDispatch>,
+				sc_consensus_grandpa::LinkHalf<
+					Block,
+					FullClient<RuntimeApi, ExecutorDispatch>,
+					FullSelectChain,
+				>,
 				sc_consensus_babe::BabeLink<Block>,
 			),
 			sc_consensus_grandpa::SharedVoterState,
 			Option<Telemetry>,
+			Arc<sc_statement_store::Store>,
+			NftIndex,
 		),
 	>,
 	ServiceError,
-> {
+>
+where
+	RuntimeApi: ConstructRuntimeApi<Block, FullClient<RuntimeApi, ExecutorDispatch>>
+		+ Send
+		+ Sync
+		+ 'static,
+	RuntimeApi::RuntimeApi:
+		RuntimeApiCollection<StateBackend = sc_client_api::StateBackendFor<FullBackend, Block>>,
+	ExecutorDispatch: NativeExecutionDispatch + 'static,
+{
 	let telemetry = config
 		.telemetry_endpoints
 		.clone()
@@ -359,6 +784,26 @@ pub fn new_partial(
 
 	let select_chain = sc_consensus::LongestChain::new(backend.clone());
 
+	let statement_store = sc_statement_store::Store::new_shared(
+		&config.data_path,
+		Default::default(),
+		client.clone(),
+		keystore_container.local_keystore(),
+		config.prometheus_registry(),
+		&task_manager.spawn_handle(),
+	)
+	.map_err(|e| ServiceError::Other(format!("Statement store error: {:?}", e)))?;
+
+	// The index is opened here to be shared with the RPC, but only `new_full_base` keeps it up to
+	// date so that the chain operations do not index the blocks they touch.
+	#[cfg(feature = "indexer")]
+	let indexer: NftIndex = Arc::new(
+		crate::indexer::Indexer::open(&config.data_path.join("ternoa-index"))
+			.map_err(|e| ServiceError::Other(format!("NFT index error: {}", e)))?,
+	);
+	#[cfg(not(feature = "indexer"))]
+	let indexer: NftIndex = ();
+
 	let transaction_pool = sc_transaction_pool::BasicPool::new_full(
 		config.transaction_pool.clone(),
 		config.role.is_authority().into(),
@@ -427,6 +872,9 @@ pub fn new_partial(
 		let select_chain = select_chain.clone();
 		let keystore = keystore_container.keystore();
 		let chain_spec = config.chain_spec.cloned_box();
+		let statement_store = statement_store.clone();
+		#[cfg(feature = "indexer")]
+		let indexer = indexer.clone();
 
 		let rpc_extensions_builder = move |deny_unsafe, subscription_executor| {
 			let deps = FullDeps {
@@ -435,6 +883,10 @@ pub fn new_partial(
 				select_chain: select_chain.clone(),
 				chain_spec: chain_spec.cloned_box(),
 				deny_unsafe,
+				command_sink: command_sink.clone(),
+				statement_store: statement_store.clone(),
+				#[cfg(feature = "indexer")]
+				indexer: indexer.clone(),
 				babe: BabeDeps {
 					keystore: keystore.clone(),
 					babe_worker_handle: babe_worker_handle.clone(),
@@ -462,6 +914,13 @@ pub fn new_partial(
 		select_chain,
 		import_queue,
 		transaction_pool,
-		other: (rpc_extensions_builder, import_setup, rpc_setup, telemetry),
+		other: (
+			rpc_extensions_builder,
+			import_setup,
+			rpc_setup,
+			telemetry,
+			statement_store,
+			indexer,
+		),
 	})
 }
\ No newline at end of file
@@ -0,0 +1,144 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The `key generate-session-keys` command.
+//!
+//! The four session keys of a validator are generated, checked with the `decode_session_keys`
+//! runtime API at the best block of the local database and only then inserted into the keystore.
+
+use std::sync::Arc;
+
+use parity_scale_codec::Encode;
+use sc_keystore::LocalKeystore;
+use sc_service::{config::KeystoreConfig, Configuration};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+	crypto::{key_types, KeyTypeId},
+	ed25519,
+	hexdisplay::HexDisplay,
+	sr25519, Pair,
+};
+use sp_keystore::Keystore;
+use sp_runtime::traits::BlakeTwo256;
+use sp_session::SessionKeys as _;
+use ternoa_client::{AbstractClient, Block, Client, ClientHandle, ExecuteWithClient};
+use ternoa_node::service::IdentifyVariant;
+
+/// Builds the SCALE encoded `SessionKeys` and `Session::set_keys` call of a runtime.
+macro_rules! encode_session_keys {
+	($runtime:ident, $grandpa:expr, $babe:expr, $im_online:expr, $authority_discovery:expr) => {{
+		let keys = $runtime::SessionKeys {
+			grandpa: $grandpa.into(),
+			babe: $babe.into(),
+			im_online: $im_online.into(),
+			authority_discovery: $authority_discovery.into(),
+		};
+		let call = $runtime::RuntimeCall::Session(pallet_session::Call::set_keys {
+			keys: keys.clone(),
+			proof: Vec::new(),
+		});
+		(keys.encode(), call.encode())
+	}};
+}
+
+/// Decodes session keys with the runtime at the best block.
+struct DecodeSessionKeys(Vec<u8>);
+
+impl ExecuteWithClient for DecodeSessionKeys {
+	type Output = Result<Option<Vec<(Vec<u8>, KeyTypeId)>>, String>;
+
+	fn execute_with_client<Client, Api, Backend>(self, client: Arc<Client>) -> Self::Output
+	where
+		<Api as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
+		Backend: sc_client_api::Backend<Block> + 'static,
+		Backend::State: sp_api::StateBackend<BlakeTwo256>,
+		Api: ternoa_client::RuntimeApiCollection<StateBackend = Backend::State>,
+		Client: AbstractClient<Block, Backend, Api = Api> + 'static,
+	{
+		let best_hash = client.info().best_hash;
+		client
+			.runtime_api()
+			.decode_session_keys(best_hash, self.0)
+			.map_err(|e| e.to_string())
+	}
+}
+
+/// Generates the session keys, inserts them into the keystore of the node and prints the
+/// `Session::set_keys` call to submit from the controller account.
+pub fn generate(config: &Configuration, client: &Client) -> sc_cli::Result<()> {
+	let KeystoreConfig::Path { path, password } = &config.keystore else {
+		return Err("Generating session keys requires a keystore on disk".into())
+	};
+
+	let (grandpa, grandpa_phrase, _) = ed25519::Pair::generate_with_phrase(None);
+	let (babe, babe_phrase, _) = sr25519::Pair::generate_with_phrase(None);
+	let (im_online, im_online_phrase, _) = sr25519::Pair::generate_with_phrase(None);
+	let (authority_discovery, authority_discovery_phrase, _) =
+		sr25519::Pair::generate_with_phrase(None);
+	let keys = [
+		(key_types::GRANDPA, grandpa_phrase, grandpa.public().to_vec()),
+		(key_types::BABE, babe_phrase, babe.public().to_vec()),
+		(key_types::IM_ONLINE, im_online_phrase, im_online.public().to_vec()),
+		(
+			key_types::AUTHORITY_DISCOVERY,
+			authority_discovery_phrase,
+			authority_discovery.public().to_vec(),
+		),
+	];
+
+	let public =
+		(grandpa.public(), babe.public(), im_online.public(), authority_discovery.public());
+	let (session_keys, set_keys) = match () {
+		#[cfg(feature = "alphanet-native")]
+		_ if config.chain_spec.is_alphanet() =>
+			encode_session_keys!(alphanet_runtime, public.0, public.1, public.2, public.3),
+		#[cfg(feature = "mainnet-native")]
+		_ if config.chain_spec.is_mainnet() =>
+			encode_session_keys!(mainnet_runtime, public.0, public.1, public.2, public.3),
+		_ => return Err("No runtime feature is enabled for this chain".into()),
+	};
+
+	let decoded = client.execute_with(DecodeSessionKeys(session_keys.clone()))?;
+	let expected = keys
+		.iter()
+		.map(|(key_type, _, public)| (public.clone(), *key_type))
+		.collect::<Vec<_>>();
+	if decoded.as_ref() != Some(&expected) {
+		return Err(format!(
+			"The runtime decodes the session keys as {:?} instead of {:?}",
+			decoded, expected
+		)
+		.into())
+	}
+
+	let keystore = LocalKeystore::open(path, password.clone())?;
+	for (key_type, phrase, public) in &keys {
+		keystore
+			.insert(*key_type, phrase, public)
+			.map_err(|_| format!("Failed to insert the {:?} key", key_type))?;
+	}
+
+	println!("Inserted the session keys into {}", path.display());
+	for (key_type, _, public) in &keys {
+		let name = std::str::from_utf8(&key_type.0).unwrap_or_default();
+		println!("  {}: 0x{}", name, HexDisplay::from(public));
+	}
+	println!("SessionKeys: 0x{}", HexDisplay::from(&session_keys));
+	println!("Unsigned Session::set_keys call: 0x{}", HexDisplay::from(&set_keys));
+
+	Ok(())
+}
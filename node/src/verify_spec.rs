@@ -0,0 +1,230 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Checks of a raw chain spec against the runtime built from this tree, run by the `verify-spec`
+//! command.
+//!
+//! The genesis hash and the `:code` hash are computed from the raw spec alone. The genesis storage
+//! built from the human spec is compared key by key with the raw one, and the mismatched values,
+//! such as session keys and balances, are decoded with the metadata of the native runtime.
+
+use std::path::{Path, PathBuf};
+
+use sc_executor::read_embedded_version;
+use sc_executor_common::runtime_blob::RuntimeBlob;
+use sc_service::Configuration;
+use sp_core::{blake2_256, hexdisplay::HexDisplay, storage::Storage};
+use sp_maybe_compressed_blob::CODE_BLOB_BOMB_LIMIT;
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash as HashT, Header as HeaderT},
+	StateVersion,
+};
+use ternoa_core_primitives::{Hash, Header};
+use ternoa_node::service::IdentifyVariant;
+
+use crate::{chain_spec, cli::VerifySpecCmd, decode, rpc::events::EventDecoder};
+
+/// The genesis hash of the live Alphanet.
+const ALPHANET_GENESIS_HASH: [u8; 32] =
+	hex_literal::hex!("18bcdb75a0bba577b084878db2dc2546eb21504eaad4b564bb7d47f9d02b6ace");
+/// The genesis hash of the live Mainnet.
+const MAINNET_GENESIS_HASH: [u8; 32] =
+	hex_literal::hex!("6859c81ca95ef624c9dfe4dc6e3381c33e5d6509e35e147092bfbc780f777c4e");
+
+/// Runs the checks and fails if any of them fails.
+pub fn run(cmd: &VerifySpecCmd, config: &Configuration) -> sc_cli::Result<()> {
+	let runtime = if config.chain_spec.is_alphanet() { "alphanet" } else { "mainnet" };
+	let mut failures = 0;
+	let mut check = |name: &str, ok: bool, details: String| {
+		println!("[{}] {}: {}", if ok { "ok" } else { "MISMATCH" }, name, details);
+		failures += !ok as u32;
+	};
+
+	let raw = config.chain_spec.as_storage_builder().build_storage()?;
+	let code = raw
+		.top
+		.get(sp_core::storage::well_known_keys::CODE)
+		.ok_or("The spec has no `:code`")?;
+	let code = sp_maybe_compressed_blob::decompress(code, CODE_BLOB_BOMB_LIMIT)
+		.map_err(|e| format!("Invalid `:code`: {}", e))?;
+	let version = RuntimeBlob::new(&code)
+		.map_err(|e| e.to_string())
+		.and_then(|blob| read_embedded_version(&blob).map_err(|e| e.to_string()))?
+		.ok_or("The `:code` has no runtime version")?;
+	println!(
+		"{} uses {} spec version {}",
+		config.chain_spec.id(),
+		version.spec_name,
+		version.spec_version
+	);
+
+	let genesis_hash = genesis_hash(&raw, version.state_version());
+	let known_hash = if config.chain_spec.is_dev() {
+		None
+	} else if config.chain_spec.is_alphanet() {
+		Some(Hash::from(ALPHANET_GENESIS_HASH))
+	} else if config.chain_spec.is_mainnet() {
+		Some(Hash::from(MAINNET_GENESIS_HASH))
+	} else {
+		None
+	};
+	match cmd.genesis_hash.or(known_hash) {
+		Some(expected) => check(
+			"genesis hash",
+			genesis_hash == expected,
+			format!("{:?}, expected {:?}", genesis_hash, expected),
+		),
+		None => println!("genesis hash: {:?}", genesis_hash),
+	}
+
+	let code_hash = Hash::from(blake2_256(&code));
+	let wasm_path = cmd
+		.wasm
+		.clone()
+		.unwrap_or_else(|| PathBuf::from(format!("wasm/{}_runtime.compact.wasm", runtime)));
+	match built_wasm(&wasm_path, config)? {
+		Some(wasm) => {
+			let built_hash = Hash::from(blake2_256(&wasm));
+			check(
+				"runtime code",
+				code_hash == built_hash,
+				format!("{:?} in the spec, {:?} in {}", code_hash, built_hash, wasm_path.display()),
+			);
+		},
+		None => println!("runtime code: {:?}, no wasm to compare with", code_hash),
+	}
+
+	let human_path = cmd
+		.human
+		.clone()
+		.unwrap_or_else(|| PathBuf::from(format!("specs/{}/{}_human.json", runtime, runtime)));
+	if human_path.exists() {
+		let human = human_storage(&human_path, config)?;
+		let (metadata, _) = decode::native_metadata(config)?;
+		let decoder = EventDecoder::new(metadata)?;
+		let mismatches = diff(&decoder, &human, &raw);
+		check(
+			"human spec",
+			mismatches.is_empty(),
+			format!("{} storage values differ from {}", mismatches.len(), human_path.display()),
+		);
+		for mismatch in mismatches {
+			println!("  {}", mismatch);
+		}
+	} else {
+		println!("human spec: {} not found, skipped", human_path.display());
+	}
+
+	if failures > 0 {
+		return Err(format!("{} checks failed", failures).into())
+	}
+
+	Ok(())
+}
+
+/// Computes the hash of the genesis block built on top of the given storage.
+fn genesis_hash(storage: &Storage, state_version: StateVersion) -> Hash {
+	let child_roots = storage.children_default.values().map(|child| {
+		let data = child.data.clone().into_iter().collect();
+		let root = BlakeTwo256::trie_root(data, state_version);
+		(child.child_info.prefixed_storage_key().into_inner(), root.as_ref().to_vec())
+	});
+	let top = storage.top.clone().into_iter().chain(child_roots).collect();
+
+	let state_root = BlakeTwo256::trie_root(top, state_version);
+	let extrinsics_root = BlakeTwo256::trie_root(Vec::new(), state_version);
+	Header::new(0, extrinsics_root, state_root, Default::default(), Default::default()).hash()
+}
+
+/// Reads the wasm built by `scripts/build_wasm.sh`, or the wasm embedded in this node if the file
+/// is missing.
+fn built_wasm(path: &Path, config: &Configuration) -> Result<Option<Vec<u8>>, String> {
+	if path.exists() {
+		let wasm =
+			std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+		return sp_maybe_compressed_blob::decompress(&wasm, CODE_BLOB_BOMB_LIMIT)
+			.map(|wasm| Some(wasm.into_owned()))
+			.map_err(|e| format!("Invalid wasm {}: {}", path.display(), e))
+	}
+
+	let embedded = match () {
+		#[cfg(feature = "alphanet-native")]
+		_ if config.chain_spec.is_alphanet() => alphanet_runtime::WASM_BINARY,
+		#[cfg(feature = "mainnet-native")]
+		_ if config.chain_spec.is_mainnet() => mainnet_runtime::WASM_BINARY,
+		_ => None,
+	};
+	embedded
+		.map(|wasm| {
+			println!("{} not found, using the wasm embedded in this node", path.display());
+			sp_maybe_compressed_blob::decompress(wasm, CODE_BLOB_BOMB_LIMIT)
+				.map(|wasm| wasm.into_owned())
+				.map_err(|e| format!("Invalid embedded wasm: {}", e))
+		})
+		.transpose()
+}
+
+/// Builds the genesis storage of a human spec.
+fn human_storage(path: &Path, config: &Configuration) -> Result<Storage, String> {
+	use sp_runtime::BuildStorage;
+
+	let path = path.to_path_buf();
+	match () {
+		#[cfg(feature = "alphanet-native")]
+		_ if config.chain_spec.is_alphanet() =>
+			chain_spec::AlphanetChainSpec::from_json_file(path)?.build_storage(),
+		#[cfg(feature = "mainnet-native")]
+		_ if config.chain_spec.is_mainnet() =>
+			chain_spec::MainnetChainSpec::from_json_file(path)?.build_storage(),
+		_ => Err("No runtime feature is enabled for this chain".into()),
+	}
+}
+
+/// Lists the top storage values that differ between the two specs, decoded when possible.
+fn diff(decoder: &EventDecoder, human: &Storage, raw: &Storage) -> Vec<String> {
+	let describe = |key: &[u8], value: Option<&Vec<u8>>| match value {
+		Some(value) => decode::storage(decoder, key, Some(value))
+			.map(|decoded| decoded["value"].to_string())
+			.unwrap_or_else(|_| format!("0x{}", HexDisplay::from(value))),
+		None => "missing".into(),
+	};
+
+	let keys = human
+		.top
+		.keys()
+		.chain(raw.top.keys())
+		.collect::<std::collections::BTreeSet<_>>();
+	keys.into_iter()
+		.filter_map(|key| {
+			let (human_value, raw_value) = (human.top.get(key), raw.top.get(key));
+			if human_value == raw_value {
+				return None
+			}
+			let key_name = decode::storage(decoder, key, None)
+				.map(|decoded| {
+					let name = |field: &str| decoded[field].as_str().unwrap_or_default().to_owned();
+					format!("{}::{} {}", name("pallet"), name("item"), decoded["keys"])
+				})
+				.unwrap_or_else(|_| format!("0x{}", HexDisplay::from(key)));
+			Some(format!(
+				"{}\n    human: {}\n    raw:   {}",
+				key_name,
+				describe(key, human_value),
+				describe(key, raw_value)
+			))
+		})
+		.collect()
+}
@@ -0,0 +1,91 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for the `ternoa_auction` pallet.
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::{marketplace::MarketplaceId, nft::NFTId};
+
+/// An auction together with its bids and timing.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AuctionState<AccountId, BlockNumber, Balance> {
+	pub nft_id: NFTId,
+	pub creator: AccountId,
+	pub marketplace_id: MarketplaceId,
+	pub start_block: BlockNumber,
+	/// The block at which the auction ends, including the extensions caused by late bids.
+	pub end_block: BlockNumber,
+	/// Whether a late bid has already extended the auction.
+	pub is_extended: bool,
+	/// Whether the auction is within `AuctionEndingPeriod` of its end and has not ended yet, in
+	/// which case a new bid may push `end_block` to at least `AuctionGracePeriod` blocks after
+	/// the bid.
+	pub is_in_ending_period: bool,
+	pub start_price: Balance,
+	pub buy_it_price: Option<Balance>,
+	pub highest_bid: Option<(AccountId, Balance)>,
+	/// The bids, from the lowest to the highest. Bounded by `BidderListLengthLimit`.
+	pub bidders: Vec<(AccountId, Balance)>,
+}
+
+impl<AccountId, BlockNumber, Balance> AuctionState<AccountId, BlockNumber, Balance> {
+	/// Converts every amount with the given function.
+	pub fn map<T>(self, f: impl Fn(Balance) -> T) -> AuctionState<AccountId, BlockNumber, T> {
+		AuctionState {
+			nft_id: self.nft_id,
+			creator: self.creator,
+			marketplace_id: self.marketplace_id,
+			start_block: self.start_block,
+			end_block: self.end_block,
+			is_extended: self.is_extended,
+			is_in_ending_period: self.is_in_ending_period,
+			start_price: f(self.start_price),
+			buy_it_price: self.buy_it_price.map(&f),
+			highest_bid: self.highest_bid.map(|(bidder, amount)| (bidder, f(amount))),
+			bidders: self.bidders.into_iter().map(|(bidder, amount)| (bidder, f(amount))).collect(),
+		}
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// Queries over the auctions stored by `ternoa_auction`.
+	pub trait AuctionApi<AccountId, BlockNumber, Balance> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+		Balance: Codec,
+	{
+		/// Returns the auction of the given NFT, if any.
+		fn auction(nft_id: NFTId) -> Option<AuctionState<AccountId, BlockNumber, Balance>>;
+
+		/// Returns all the running auctions.
+		fn auctions() -> Vec<AuctionState<AccountId, BlockNumber, Balance>>;
+
+		/// Returns the amount `account` can withdraw with `claim`.
+		fn claimable_balance(account: AccountId) -> Balance;
+
+		/// Returns every account that has something to claim, with the claimable amount.
+		fn claimable_balances() -> Vec<(AccountId, Balance)>;
+	}
+}
@@ -0,0 +1,120 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for the `ternoa_bridge` pallet.
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Identifier of a chain connected to the bridge.
+pub type ChainId = u8;
+/// Counter of the deposits made towards a chain.
+pub type DepositNonce = u64;
+
+/// A deposit proposal that has not reached `RelayerVoteThreshold` yet.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct OpenProposal<AccountId, BlockNumber, Balance> {
+	pub deposit_nonce: DepositNonce,
+	pub recipient: AccountId,
+	pub amount: Balance,
+	/// The relayers that voted for the proposal.
+	pub voters: Vec<AccountId>,
+	pub expiry: BlockNumber,
+	/// The number of blocks left before the proposal expires, out of `ProposalLifetime`.
+	pub remaining_lifetime: BlockNumber,
+}
+
+impl<AccountId, BlockNumber, Balance> OpenProposal<AccountId, BlockNumber, Balance> {
+	/// Converts the amount with the given function.
+	pub fn map<T>(self, f: impl Fn(Balance) -> T) -> OpenProposal<AccountId, BlockNumber, T> {
+		OpenProposal {
+			deposit_nonce: self.deposit_nonce,
+			recipient: self.recipient,
+			amount: f(self.amount),
+			voters: self.voters,
+			expiry: self.expiry,
+			remaining_lifetime: self.remaining_lifetime,
+		}
+	}
+}
+
+/// The bridge state for one chain.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ChainStatus<AccountId, BlockNumber, Balance> {
+	pub chain_id: ChainId,
+	pub deposit_nonce: DepositNonce,
+	pub open_proposals: Vec<OpenProposal<AccountId, BlockNumber, Balance>>,
+}
+
+impl<AccountId, BlockNumber, Balance> ChainStatus<AccountId, BlockNumber, Balance> {
+	/// Converts the amounts with the given function.
+	pub fn map<T>(self, f: impl Fn(Balance) -> T) -> ChainStatus<AccountId, BlockNumber, T> {
+		ChainStatus {
+			chain_id: self.chain_id,
+			deposit_nonce: self.deposit_nonce,
+			open_proposals: self.open_proposals.into_iter().map(|p| p.map(&f)).collect(),
+		}
+	}
+}
+
+/// The bridge configuration and the state of every whitelisted chain.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct BridgeStatus<AccountId, BlockNumber, Balance> {
+	pub bridge_fee: Balance,
+	pub relayers: Vec<AccountId>,
+	pub relayer_vote_threshold: u32,
+	pub proposal_lifetime: BlockNumber,
+	pub chains: Vec<ChainStatus<AccountId, BlockNumber, Balance>>,
+}
+
+impl<AccountId, BlockNumber, Balance> BridgeStatus<AccountId, BlockNumber, Balance> {
+	/// Converts the amounts with the given function.
+	pub fn map<T>(self, f: impl Fn(Balance) -> T) -> BridgeStatus<AccountId, BlockNumber, T> {
+		BridgeStatus {
+			bridge_fee: f(self.bridge_fee),
+			relayers: self.relayers,
+			relayer_vote_threshold: self.relayer_vote_threshold,
+			proposal_lifetime: self.proposal_lifetime,
+			chains: self.chains.into_iter().map(|chain| chain.map(&f)).collect(),
+		}
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// Queries over the chains and proposals stored by `ternoa_bridge`.
+	pub trait BridgeApi<AccountId, BlockNumber, Balance> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+		Balance: Codec,
+	{
+		/// Returns the bridge configuration and the state of every whitelisted chain.
+		fn bridge_status() -> BridgeStatus<AccountId, BlockNumber, Balance>;
+
+		/// Returns the state of the given chain, if it is whitelisted.
+		fn chain_status(chain_id: ChainId) -> Option<ChainStatus<AccountId, BlockNumber, Balance>>;
+	}
+}
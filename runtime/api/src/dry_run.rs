@@ -0,0 +1,48 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition used to dry-run extrinsics.
+//!
+//! The extrinsic itself is applied through `BlockBuilder::apply_extrinsic`; this API only reads
+//! back what it left in `frame_system`.
+
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+use sp_weights::Weight;
+
+/// What an applied extrinsic left in `frame_system`.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct AppliedExtrinsic {
+	/// The SCALE encoded `RuntimeEvent`s deposited by the extrinsic.
+	pub events: Vec<Vec<u8>>,
+	/// The weight reported by `ExtrinsicSuccess` or `ExtrinsicFailed`, if the extrinsic was
+	/// dispatched.
+	pub actual_weight: Option<Weight>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Reads the effects of an extrinsic applied on top of a block.
+	pub trait DryRunApi {
+		/// Returns the number of events deposited so far.
+		fn event_count() -> u32;
+
+		/// Returns the events deposited after the first `event_count` ones and the weight of the
+		/// extrinsic that deposited them.
+		fn applied_extrinsic(event_count: u32) -> AppliedExtrinsic;
+	}
+}
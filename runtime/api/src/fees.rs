@@ -0,0 +1,132 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition estimating everything a call costs its signer.
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Where an amount charged to the signer goes.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum ChargeDestination<AccountId> {
+	/// The weight and length fees, handled by `OnChargeTransaction`.
+	TransactionFees,
+	/// The treasury, through the `FeesCollector` of the pallet.
+	Treasury,
+	/// The account of the pallet handling the call.
+	PalletAccount,
+	/// Reserved on the signer's account.
+	Reserved,
+	/// Another account, e.g. the seller of an NFT.
+	Account(AccountId),
+}
+
+/// What an amount charged to the signer is for.
+#[derive(Encode, Decode, TypeInfo, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum ChargeReason {
+	/// The weight and length fees of the extrinsic.
+	TransactionFee,
+	/// The fee to mint an NFT.
+	NftMintFee,
+	/// The fee to mint or add a secret NFT.
+	SecretNftMintFee,
+	/// The fee to mint or convert to a capsule.
+	CapsuleMintFee,
+	/// The fee to create a marketplace.
+	MarketplaceMintFee,
+	/// The price of an NFT, minus the commission and the royalty.
+	SellerProceeds,
+	/// The commission of the marketplace an NFT is sold on.
+	MarketplaceCommission,
+	/// The royalty paid to the creator of an NFT.
+	Royalty,
+	/// An auction bid, held by the pallet until the auction ends.
+	Bid,
+	/// The fee to deposit to the bridge.
+	BridgeFee,
+	/// The amount sent through the bridge.
+	BridgedAmount,
+	/// The fee to set an `AtBlock` transmission protocol.
+	AtBlockFee,
+	/// The fee to set an `AtBlockWithReset` transmission protocol.
+	AtBlockWithResetFee,
+	/// The fee to set an `OnConsent` transmission protocol.
+	OnConsentFee,
+	/// The fee to set an `OnConsentAtBlock` transmission protocol.
+	OnConsentAtBlockFee,
+	/// The stake reserved when registering a TEE enclave.
+	TeeStake,
+}
+
+/// An amount charged to the signer.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct Charge<AccountId, Balance> {
+	pub destination: ChargeDestination<AccountId>,
+	/// What the amount is for.
+	pub reason: ChargeReason,
+	pub amount: Balance,
+}
+
+/// Everything a call costs its signer.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct CostEstimate<AccountId, Balance> {
+	pub charges: Vec<Charge<AccountId, Balance>>,
+	/// The sum of the charges.
+	pub total: Balance,
+}
+
+impl<AccountId, Balance> CostEstimate<AccountId, Balance> {
+	/// Converts the amounts with the given function.
+	pub fn map<T>(self, f: impl Fn(Balance) -> T) -> CostEstimate<AccountId, T> {
+		CostEstimate {
+			charges: self
+				.charges
+				.into_iter()
+				.map(|charge| Charge {
+					destination: charge.destination,
+					reason: charge.reason,
+					amount: f(charge.amount),
+				})
+				.collect(),
+			total: f(self.total),
+		}
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// Estimates the total cost of a call, protocol fees included.
+	pub trait FeesApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Returns the charges of the SCALE encoded `RuntimeCall`, or `None` if it cannot be
+		/// decoded. Calls without protocol fees only have a transaction fee charge.
+		fn cost_estimate(encoded_call: Vec<u8>) -> Option<CostEstimate<AccountId, Balance>>;
+	}
+}
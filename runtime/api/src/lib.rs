@@ -0,0 +1,66 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime APIs exposing the state of the Ternoa pallets to the node.
+//!
+//! The types returned by these APIs are flattened views of the pallet storage so that the node
+//! and its RPC layer do not have to depend on the pallets themselves.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+pub mod auction;
+pub mod bridge;
+pub mod dry_run;
+pub mod fees;
+pub mod marketplace;
+pub mod metrics;
+pub mod nft;
+pub mod rent;
+pub mod staking_rewards;
+pub mod statement;
+pub mod tee;
+pub mod transmission_protocols;
+
+/// The largest number of storage entries that a paginated query scans in one call.
+pub const MAX_PAGE_SIZE: u32 = 1_000;
+
+/// The items found in a slice of a storage map.
+///
+/// A paginated query scans at most `limit` entries of the map, so a page can hold fewer items
+/// than `limit`, or none, while more remain: keep querying from `next` until it is `None`.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct Page<Key, Item> {
+	pub items: Vec<Item>,
+	/// The key to start the next page after, `None` once the whole map is scanned.
+	pub next: Option<Key>,
+}
+
+impl<Key, Item> Page<Key, Item> {
+	/// Converts every item with the given function.
+	pub fn map<T>(self, f: impl FnMut(Item) -> T) -> Page<Key, T> {
+		Page { items: self.items.into_iter().map(f).collect(), next: self.next }
+	}
+}
@@ -0,0 +1,110 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for the `ternoa_marketplace` pallet.
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::{nft::NFTId, Page};
+
+/// Identifier of a marketplace.
+pub type MarketplaceId = u32;
+
+/// An NFT listed for sale on a marketplace.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct Listing<AccountId, Balance> {
+	pub nft_id: NFTId,
+	pub marketplace_id: MarketplaceId,
+	pub seller: AccountId,
+	pub price: Balance,
+}
+
+impl<AccountId, Balance> Listing<AccountId, Balance> {
+	/// Converts the price with the given function.
+	pub fn map<T>(self, f: impl Fn(Balance) -> T) -> Listing<AccountId, T> {
+		Listing {
+			nft_id: self.nft_id,
+			marketplace_id: self.marketplace_id,
+			seller: self.seller,
+			price: f(self.price),
+		}
+	}
+}
+
+/// Breakdown of what buying a listed NFT with `buy_nft` costs and where the funds go.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct BuyQuote<AccountId, Balance> {
+	/// The listing price, paid in full by the buyer.
+	pub price: Balance,
+	pub seller: AccountId,
+	/// What the seller receives once the commission and the royalty are deducted.
+	pub seller_proceeds: Balance,
+	pub marketplace_owner: AccountId,
+	pub marketplace_commission: Balance,
+	/// The creator of the NFT, who receives the royalty.
+	pub creator: AccountId,
+	pub royalty: Balance,
+	/// Estimated fee of the `buy_nft` extrinsic itself.
+	pub transaction_fee: Balance,
+	/// Everything that leaves the buyer's account: `price + transaction_fee`.
+	pub total: Balance,
+}
+
+impl<AccountId, Balance> BuyQuote<AccountId, Balance> {
+	/// Converts every amount with the given function.
+	pub fn map<T>(self, f: impl Fn(Balance) -> T) -> BuyQuote<AccountId, T> {
+		BuyQuote {
+			price: f(self.price),
+			seller: self.seller,
+			seller_proceeds: f(self.seller_proceeds),
+			marketplace_owner: self.marketplace_owner,
+			marketplace_commission: f(self.marketplace_commission),
+			creator: self.creator,
+			royalty: f(self.royalty),
+			transaction_fee: f(self.transaction_fee),
+			total: f(self.total),
+		}
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// Queries over the listings stored by `ternoa_marketplace`.
+	pub trait MarketplaceApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Returns the NFTs listed on the given marketplace among at most `limit` listings
+		/// following the NFT `start_after`.
+		fn listings(
+			marketplace_id: MarketplaceId,
+			start_after: Option<NFTId>,
+			limit: u32,
+		) -> Page<NFTId, Listing<AccountId, Balance>>;
+
+		/// Returns what buying the given NFT would cost, or `None` if it is not listed.
+		fn buy_quote(nft_id: NFTId) -> Option<BuyQuote<AccountId, Balance>>;
+	}
+}
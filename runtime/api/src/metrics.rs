@@ -0,0 +1,58 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for the counters published by the node as Prometheus metrics.
+
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::marketplace::MarketplaceId;
+
+/// Counters over the state of the Ternoa pallets.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ChainMetrics {
+	pub nfts: u32,
+	pub collections: u32,
+	pub secret_nfts: u32,
+	pub capsules: u32,
+	/// The number of NFTs listed for sale on each marketplace that has listings.
+	pub listings: Vec<(MarketplaceId, u32)>,
+	pub auctions: u32,
+	pub rent_contracts: u32,
+	pub pending_transmissions: u32,
+	/// The number of TEE clusters with at least one free slot.
+	pub clusters_with_free_slots: u32,
+	/// The number of bridge proposals still waiting for relayer votes.
+	pub pending_bridge_proposals: u32,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Counters over the Ternoa pallets, meant for monitoring.
+	pub trait MetricsApi {
+		/// Returns the counters at the given block.
+		///
+		/// This iterates over most of the Ternoa storage, so it should not be called on every
+		/// block during a major sync.
+		fn chain_metrics() -> ChainMetrics;
+	}
+}
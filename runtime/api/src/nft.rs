@@ -0,0 +1,122 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for the `ternoa_nft` pallet.
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{Permill, RuntimeDebug};
+use sp_std::vec::Vec;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::Page;
+
+/// Identifier of an NFT.
+pub type NFTId = u32;
+/// Identifier of a collection.
+pub type CollectionId = u32;
+
+/// Flags describing what an NFT is currently being used for.
+#[derive(Encode, Decode, TypeInfo, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct NftState {
+	pub is_capsule: bool,
+	pub is_secret: bool,
+	pub is_soulbound: bool,
+	pub is_listed: bool,
+	pub is_rented: bool,
+	pub is_delegated: bool,
+	pub is_in_transmission: bool,
+}
+
+/// An NFT together with its state.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct NftDetails<AccountId> {
+	pub nft_id: NFTId,
+	pub owner: AccountId,
+	pub creator: AccountId,
+	pub offchain_data: Vec<u8>,
+	pub royalty: Permill,
+	pub collection_id: Option<CollectionId>,
+	/// The account the NFT is delegated to, if any.
+	pub delegated_to: Option<AccountId>,
+	pub state: NftState,
+}
+
+/// A collection together with the NFTs it contains.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct CollectionDetails<AccountId> {
+	pub collection_id: CollectionId,
+	pub owner: AccountId,
+	pub offchain_data: Vec<u8>,
+	pub nfts: Vec<NFTId>,
+	pub limit: Option<u32>,
+	pub is_closed: bool,
+}
+
+/// The fees currently charged to mint the different kinds of NFTs.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct MintFees<Balance> {
+	pub nft: Balance,
+	pub secret_nft: Balance,
+	pub capsule: Balance,
+}
+
+impl<Balance> MintFees<Balance> {
+	/// Converts every fee with the given function.
+	pub fn map<T>(self, f: impl Fn(Balance) -> T) -> MintFees<T> {
+		MintFees { nft: f(self.nft), secret_nft: f(self.secret_nft), capsule: f(self.capsule) }
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// Queries over the NFTs and collections stored by `ternoa_nft`.
+	pub trait NftApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Returns the NFT with the given id, if it exists.
+		fn nft(nft_id: NFTId) -> Option<NftDetails<AccountId>>;
+
+		/// Returns the NFTs owned by `owner` among at most `limit` NFTs following `start_after`.
+		fn nfts_by_owner(
+			owner: AccountId,
+			start_after: Option<NFTId>,
+			limit: u32,
+		) -> Page<NFTId, NftDetails<AccountId>>;
+
+		/// Returns the state of the NFT with the given id, if it exists.
+		fn nft_state(nft_id: NFTId) -> Option<NftState>;
+
+		/// Returns the collection with the given id, if it exists.
+		fn collection(collection_id: CollectionId) -> Option<CollectionDetails<AccountId>>;
+
+		/// Returns all the NFTs that belong to the given collection.
+		fn collection_nfts(collection_id: CollectionId) -> Vec<NftDetails<AccountId>>;
+
+		/// Returns the current NFT, secret NFT and capsule mint fees.
+		fn mint_fees() -> MintFees<Balance>;
+	}
+}
@@ -0,0 +1,136 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for the `ternoa_rent` pallet.
+//!
+//! Following the pallet's naming, the renter is the owner of the NFT who offers it for rent and
+//! the rentee is the account renting it.
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::{nft::NFTId, Page};
+
+/// What the rentee pays for each rent period.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum RentFee<Balance> {
+	Tokens(Balance),
+	Nft(NFTId),
+}
+
+impl<Balance> RentFee<Balance> {
+	/// Converts the amount with the given function.
+	pub fn map<T>(self, f: impl Fn(Balance) -> T) -> RentFee<T> {
+		match self {
+			RentFee::Tokens(amount) => RentFee::Tokens(f(amount)),
+			RentFee::Nft(nft_id) => RentFee::Nft(nft_id),
+		}
+	}
+}
+
+/// How long a contract lasts.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum RentDuration<BlockNumber> {
+	/// The contract lasts for the given number of blocks and is paid upfront.
+	Fixed(BlockNumber),
+	/// The contract is paid every given number of blocks.
+	Subscription(BlockNumber),
+}
+
+/// The next payment of a subscription contract.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct SubscriptionPayment<BlockNumber, Balance> {
+	pub block: BlockNumber,
+	pub amount: RentFee<Balance>,
+}
+
+/// A rent contract together with its open offers.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct RentContract<AccountId, BlockNumber, Balance> {
+	pub nft_id: NFTId,
+	pub renter: AccountId,
+	pub rentee: Option<AccountId>,
+	/// The block at which the contract started, `None` while it is still available.
+	pub start_block: Option<BlockNumber>,
+	pub duration: RentDuration<BlockNumber>,
+	pub rent_fee: RentFee<Balance>,
+	pub renter_can_revoke: bool,
+	/// The accounts that made an offer on this contract.
+	pub offers: Vec<AccountId>,
+	/// For running subscription contracts, when the next payment is due and how much it is.
+	pub next_payment: Option<SubscriptionPayment<BlockNumber, Balance>>,
+}
+
+impl<AccountId, BlockNumber, Balance> RentContract<AccountId, BlockNumber, Balance> {
+	/// Converts every amount with the given function.
+	pub fn map<T>(self, f: impl Fn(Balance) -> T) -> RentContract<AccountId, BlockNumber, T> {
+		RentContract {
+			nft_id: self.nft_id,
+			renter: self.renter,
+			rentee: self.rentee,
+			start_block: self.start_block,
+			duration: self.duration,
+			rent_fee: self.rent_fee.map(&f),
+			renter_can_revoke: self.renter_can_revoke,
+			offers: self.offers,
+			next_payment: self.next_payment.map(|payment| SubscriptionPayment {
+				block: payment.block,
+				amount: payment.amount.map(&f),
+			}),
+		}
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// Queries over the contracts stored by `ternoa_rent`.
+	pub trait RentApi<AccountId, BlockNumber, Balance> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+		Balance: Codec,
+	{
+		/// Returns the contract of the given NFT, if any.
+		fn contract(nft_id: NFTId) -> Option<RentContract<AccountId, BlockNumber, Balance>>;
+
+		/// Returns the contracts created by `renter`, the owner of the rented NFTs, among at most
+		/// `limit` contracts following the NFT `start_after`.
+		fn contracts_by_renter(
+			renter: AccountId,
+			start_after: Option<NFTId>,
+			limit: u32,
+		) -> Page<NFTId, RentContract<AccountId, BlockNumber, Balance>>;
+
+		/// Returns the contracts in which `rentee` is renting the NFT, among at most `limit`
+		/// contracts following the NFT `start_after`.
+		fn contracts_by_rentee(
+			rentee: AccountId,
+			start_after: Option<NFTId>,
+			limit: u32,
+		) -> Page<NFTId, RentContract<AccountId, BlockNumber, Balance>>;
+	}
+}
@@ -0,0 +1,76 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for the era payouts computed by `ternoa_staking_rewards`.
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use ternoa_core_primitives::EraIndex;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// The payout `ternoa_staking_rewards` would compute if the active era ended now.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct EraRewardEstimate<Balance> {
+	pub era: EraIndex,
+	pub total_staked: Balance,
+	pub total_issuance: Balance,
+	/// The nominal duration of an era, as passed to `EraPayout`.
+	pub era_duration_millis: u64,
+	/// The payout per session set by `set_session_era_payout`.
+	pub session_era_payout: Balance,
+	/// The extra payout per session set by `set_session_extra_reward_payout`.
+	pub session_extra_reward_payout: Balance,
+	/// The amount shared between the validators and their nominators.
+	pub validator_payout: Balance,
+	/// The amount sent to `RewardRemainder`.
+	pub remainder: Balance,
+}
+
+impl<Balance> EraRewardEstimate<Balance> {
+	/// Converts the amounts with the given function.
+	pub fn map<T>(self, f: impl Fn(Balance) -> T) -> EraRewardEstimate<T> {
+		EraRewardEstimate {
+			era: self.era,
+			total_staked: f(self.total_staked),
+			total_issuance: f(self.total_issuance),
+			era_duration_millis: self.era_duration_millis,
+			session_era_payout: f(self.session_era_payout),
+			session_extra_reward_payout: f(self.session_extra_reward_payout),
+			validator_payout: f(self.validator_payout),
+			remainder: f(self.remainder),
+		}
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// Queries over the era rewards paid through `ternoa_staking_rewards`.
+	pub trait StakingRewardsApi<Balance> where
+		Balance: Codec,
+	{
+		/// Returns the payout of the active era as if it ended now, or `None` before the first
+		/// era starts.
+		fn active_era_reward_estimate() -> Option<EraRewardEstimate<Balance>>;
+
+		/// Returns the amount paid to the validators of a past era, if it is still in the staking
+		/// history.
+		fn era_validator_reward(era: EraIndex) -> Option<Balance>;
+	}
+}
@@ -0,0 +1,84 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Conventions of the statements gossiped through the node statement store.
+//!
+//! Statements are only accepted from registered TEE enclaves and their operators, and from NFT
+//! owners. NFT owners prove their ownership by tagging their statements with the topic of the
+//! NFT they are about, built by [`nft_topic`].
+
+use crate::nft::NFTId;
+
+/// A statement topic.
+pub type Topic = [u8; 32];
+
+/// Prefix of the topics referring to an NFT.
+pub const NFT_TOPIC_PREFIX: [u8; 4] = *b"tnft";
+
+/// Returns the topic referring to the given NFT.
+pub fn nft_topic(nft_id: NFTId) -> Topic {
+	let mut topic = [0u8; 32];
+	topic[..4].copy_from_slice(&NFT_TOPIC_PREFIX);
+	topic[4..8].copy_from_slice(&nft_id.to_le_bytes());
+	topic
+}
+
+/// Returns the NFT the given topic refers to, if it was built by [`nft_topic`].
+pub fn nft_from_topic(topic: &Topic) -> Option<NFTId> {
+	if topic[..4] != NFT_TOPIC_PREFIX || topic[8..].iter().any(|byte| *byte != 0) {
+		return None
+	}
+
+	let mut nft_id = [0u8; 4];
+	nft_id.copy_from_slice(&topic[4..8]);
+	Some(NFTId::from_le_bytes(nft_id))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn nft_topic_round_trips() {
+		for nft_id in [0, 1, 42, NFTId::MAX] {
+			assert_eq!(nft_from_topic(&nft_topic(nft_id)), Some(nft_id));
+		}
+	}
+
+	#[test]
+	fn nft_topic_layout() {
+		let topic = nft_topic(0x0403_0201);
+		assert_eq!(&topic[..8], b"tnft\x01\x02\x03\x04");
+		assert_eq!(topic[8..], [0u8; 24]);
+	}
+
+	#[test]
+	fn nft_from_topic_rejects_wrong_prefix() {
+		let mut topic = nft_topic(7);
+		topic[..4].copy_from_slice(b"tee!");
+		assert_eq!(nft_from_topic(&topic), None);
+		assert_eq!(nft_from_topic(&[0u8; 32]), None);
+	}
+
+	#[test]
+	fn nft_from_topic_rejects_non_zero_padding() {
+		for index in 8..32 {
+			let mut topic = nft_topic(7);
+			topic[index] = 1;
+			assert_eq!(nft_from_topic(&topic), None);
+		}
+	}
+}
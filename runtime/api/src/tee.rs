@@ -0,0 +1,148 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for the `ternoa_tee` pallet.
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+use ternoa_core_primitives::EraIndex;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::nft::NFTId;
+
+/// Identifier of a cluster.
+pub type ClusterId = u32;
+/// Position of an enclave in its cluster.
+pub type SlotId = u32;
+
+/// An enclave and where it is assigned.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct EnclaveInfo<AccountId> {
+	pub operator: AccountId,
+	/// The account the enclave signs with.
+	pub enclave_address: AccountId,
+	pub api_uri: Vec<u8>,
+	pub cluster_id: Option<ClusterId>,
+	pub slot_id: Option<SlotId>,
+}
+
+/// A cluster and its slot assignments.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ClusterInfo<AccountId> {
+	pub cluster_id: ClusterId,
+	/// The operators of the enclaves in the cluster, with their slot.
+	pub enclaves: Vec<(AccountId, SlotId)>,
+	/// The number of slots still available, out of `ClusterSize`.
+	pub free_slots: u32,
+}
+
+/// The requests waiting for the technical committee.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PendingRequests<AccountId> {
+	pub registrations: Vec<EnclaveInfo<AccountId>>,
+	pub updates: Vec<EnclaveInfo<AccountId>>,
+	/// The operators that asked to unregister their enclave.
+	pub unregistrations: Vec<AccountId>,
+}
+
+/// A report sent by a metrics server about an enclave.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct MetricsReport<AccountId> {
+	pub era: EraIndex,
+	pub submitted_by: AccountId,
+	/// The five report parameters, weighted by `ReportParamsWeightages` to compute rewards.
+	pub params: Vec<u8>,
+}
+
+/// Estimate of the rewards an operator can still claim.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct RewardsEstimate<Balance> {
+	/// The eras within `TeeHistoryDepth` that have not been claimed yet.
+	pub unclaimed_eras: Vec<EraIndex>,
+	/// The amount the operator would get for these eras if the daily reward pool was shared
+	/// equally among all the assigned enclaves. The actual amount depends on the metrics reports.
+	pub estimated_amount: Balance,
+}
+
+impl<Balance> RewardsEstimate<Balance> {
+	/// Converts the amount with the given function.
+	pub fn map<T>(self, f: impl Fn(Balance) -> T) -> RewardsEstimate<T> {
+		RewardsEstimate {
+			unclaimed_eras: self.unclaimed_eras,
+			estimated_amount: f(self.estimated_amount),
+		}
+	}
+}
+
+/// An enclave that stores a shard of a secret NFT or a capsule.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ShardHolder<AccountId> {
+	pub enclave_address: AccountId,
+	pub operator: Option<AccountId>,
+	pub cluster_id: Option<ClusterId>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Queries over the clusters, enclaves and rewards stored by `ternoa_tee`.
+	pub trait TeeApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Returns all the clusters.
+		fn clusters() -> Vec<ClusterInfo<AccountId>>;
+
+		/// Returns the cluster with the given id, if it exists.
+		fn cluster(cluster_id: ClusterId) -> Option<ClusterInfo<AccountId>>;
+
+		/// Returns the enclave registered by `operator`, if any.
+		fn enclave(operator: AccountId) -> Option<EnclaveInfo<AccountId>>;
+
+		/// Returns the operator of the enclave signing with `enclave_address`.
+		fn enclave_operator(enclave_address: AccountId) -> Option<AccountId>;
+
+		/// Returns the registrations, updates and unregistrations awaiting approval.
+		fn pending_requests() -> PendingRequests<AccountId>;
+
+		/// Returns the metrics reports of the enclave of `operator` for the current and previous
+		/// eras.
+		fn latest_metrics_reports(operator: AccountId) -> Vec<MetricsReport<AccountId>>;
+
+		/// Returns an estimate of the rewards `operator` can claim.
+		fn claimable_rewards(operator: AccountId) -> RewardsEstimate<Balance>;
+
+		/// Returns the enclaves holding a shard of the given secret NFT.
+		fn secret_nft_shard_holders(nft_id: NFTId) -> Vec<ShardHolder<AccountId>>;
+
+		/// Returns the enclaves holding a shard of the given capsule.
+		fn capsule_shard_holders(nft_id: NFTId) -> Vec<ShardHolder<AccountId>>;
+	}
+}
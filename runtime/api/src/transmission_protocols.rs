@@ -0,0 +1,84 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for the `ternoa_transmission_protocols` pallet.
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::{nft::NFTId, Page};
+
+/// The protocol that triggers a transmission.
+#[derive(Encode, Decode, TypeInfo, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ProtocolKind {
+	AtBlock,
+	AtBlockWithReset,
+	OnConsent,
+	OnConsentAtBlock,
+}
+
+/// An account of a consent list and whether it has consented yet.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct Consent<AccountId> {
+	pub account: AccountId,
+	pub has_consented: bool,
+}
+
+/// A transmission that has not been executed yet.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PendingTransmission<AccountId, BlockNumber> {
+	pub nft_id: NFTId,
+	/// The current owner of the NFT.
+	pub owner: AccountId,
+	pub recipient: AccountId,
+	pub kind: ProtocolKind,
+	/// The block at which the NFT is transmitted, for the at block protocols.
+	pub trigger_block: Option<BlockNumber>,
+	/// The accounts that must consent, for the on consent protocols. Bounded by
+	/// `MaxConsentListSize`.
+	pub consent_list: Vec<Consent<AccountId>>,
+	/// The number of consents needed to transmit the NFT, for the on consent protocols.
+	pub threshold: Option<u8>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Queries over the transmissions stored by `ternoa_transmission_protocols`.
+	pub trait TransmissionProtocolsApi<AccountId, BlockNumber> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// Returns the pending transmission of the given NFT, if any.
+		fn transmission(nft_id: NFTId) -> Option<PendingTransmission<AccountId, BlockNumber>>;
+
+		/// Returns the pending transmissions that will send an NFT to `recipient` among at most
+		/// `limit` transmissions following the NFT `start_after`.
+		fn transmissions_by_recipient(
+			recipient: AccountId,
+			start_after: Option<NFTId>,
+			limit: u32,
+		) -> Page<NFTId, PendingTransmission<AccountId, BlockNumber>>;
+	}
+}
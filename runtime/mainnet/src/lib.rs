@@ -26,6 +26,9 @@ pub use ternoa_runtime_common::constants as common_constants;
 
 pub mod constants;
 mod pallets;
+pub mod runtime_apis;
+#[cfg(feature = "try-runtime")]
+mod try_state;
 mod version;
 mod weights;
 
@@ -51,8 +54,25 @@ use sp_runtime::{
 };
 use sp_std::prelude::*;
 use sp_version::RuntimeVersion;
-use ternoa_core_primitives::{AccountId, Balance, BlockNumber, Index, Signature, Hash};
+use ternoa_core_primitives::{AccountId, Balance, BlockNumber, EraIndex, Index, Signature, Hash};
 use ternoa_runtime_common::{impl_runtime_weights, BlockLength};
+use ternoa_runtime_api::{
+	auction::{AuctionApi, AuctionState},
+	bridge::{BridgeApi, BridgeStatus, ChainId, ChainStatus},
+	dry_run::{AppliedExtrinsic, DryRunApi},
+	fees::{CostEstimate, FeesApi},
+	marketplace::{BuyQuote, Listing, MarketplaceApi, MarketplaceId},
+	metrics::{ChainMetrics, MetricsApi},
+	nft::{CollectionDetails, CollectionId, MintFees, NFTId, NftApi, NftDetails, NftState},
+	rent::{RentApi, RentContract},
+	staking_rewards::{EraRewardEstimate, StakingRewardsApi},
+	tee::{
+		ClusterId, ClusterInfo, EnclaveInfo, MetricsReport, PendingRequests, RewardsEstimate,
+		ShardHolder, TeeApi,
+	},
+	transmission_protocols::{PendingTransmission, TransmissionProtocolsApi},
+	Page,
+};
 pub use version::VERSION;
 
 #[cfg(feature = "std")]
@@ -455,6 +475,271 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl NftApi<Block, AccountId, Balance> for Runtime {
+		fn nft(nft_id: NFTId) -> Option<NftDetails<AccountId>> {
+			NFT::nfts(nft_id).map(|data| runtime_apis::nft_details(nft_id, data))
+		}
+
+		fn nfts_by_owner(
+			owner: AccountId,
+			start_after: Option<NFTId>,
+			limit: u32,
+		) -> Page<NFTId, NftDetails<AccountId>> {
+			runtime_apis::scan_page::<ternoa_nft::Nfts<Runtime>, _, _, _>(
+				start_after,
+				limit,
+				|nft_id, data| {
+					(data.owner == owner).then(|| runtime_apis::nft_details(*nft_id, data))
+				},
+			)
+		}
+
+		fn nft_state(nft_id: NFTId) -> Option<NftState> {
+			NFT::nfts(nft_id).map(|data| runtime_apis::nft_state(&data))
+		}
+
+		fn collection(collection_id: CollectionId) -> Option<CollectionDetails<AccountId>> {
+			NFT::collections(collection_id)
+				.map(|collection| runtime_apis::collection_details(collection_id, collection))
+		}
+
+		fn collection_nfts(collection_id: CollectionId) -> Vec<NftDetails<AccountId>> {
+			NFT::collections(collection_id)
+				.map(|collection| collection.nfts.into_inner())
+				.unwrap_or_default()
+				.into_iter()
+				.filter_map(|nft_id| {
+					NFT::nfts(nft_id).map(|data| runtime_apis::nft_details(nft_id, data))
+				})
+				.collect()
+		}
+
+		fn mint_fees() -> MintFees<Balance> {
+			MintFees {
+				nft: NFT::nft_mint_fee(),
+				secret_nft: NFT::secret_nft_mint_fee(),
+				capsule: NFT::capsule_mint_fee(),
+			}
+		}
+	}
+
+	impl MarketplaceApi<Block, AccountId, Balance> for Runtime {
+		fn listings(
+			marketplace_id: MarketplaceId,
+			start_after: Option<NFTId>,
+			limit: u32,
+		) -> Page<NFTId, Listing<AccountId, Balance>> {
+			runtime_apis::scan_page::<ternoa_marketplace::ListedNfts<Runtime>, _, _, _>(
+				start_after,
+				limit,
+				|nft_id, sale| {
+					(sale.marketplace_id == marketplace_id)
+						.then(|| runtime_apis::listing(*nft_id, sale))
+				},
+			)
+		}
+
+		fn buy_quote(nft_id: NFTId) -> Option<BuyQuote<AccountId, Balance>> {
+			runtime_apis::buy_quote(nft_id)
+		}
+	}
+
+	impl AuctionApi<Block, AccountId, BlockNumber, Balance> for Runtime {
+		fn auction(nft_id: NFTId) -> Option<AuctionState<AccountId, BlockNumber, Balance>> {
+			Auction::auctions(nft_id).map(|auction| runtime_apis::auction_state(nft_id, auction))
+		}
+
+		fn auctions() -> Vec<AuctionState<AccountId, BlockNumber, Balance>> {
+			ternoa_auction::Auctions::<Runtime>::iter()
+				.map(|(nft_id, auction)| runtime_apis::auction_state(nft_id, auction))
+				.collect()
+		}
+
+		fn claimable_balance(account: AccountId) -> Balance {
+			Auction::claims(account).unwrap_or_default()
+		}
+
+		fn claimable_balances() -> Vec<(AccountId, Balance)> {
+			ternoa_auction::Claims::<Runtime>::iter().collect()
+		}
+	}
+
+	impl RentApi<Block, AccountId, BlockNumber, Balance> for Runtime {
+		fn contract(nft_id: NFTId) -> Option<RentContract<AccountId, BlockNumber, Balance>> {
+			Rent::contracts(nft_id).map(|contract| runtime_apis::rent_contract(nft_id, contract))
+		}
+
+		fn contracts_by_renter(
+			renter: AccountId,
+			start_after: Option<NFTId>,
+			limit: u32,
+		) -> Page<NFTId, RentContract<AccountId, BlockNumber, Balance>> {
+			runtime_apis::scan_page::<ternoa_rent::Contracts<Runtime>, _, _, _>(
+				start_after,
+				limit,
+				|nft_id, contract| {
+					(contract.renter == renter)
+						.then(|| runtime_apis::rent_contract(*nft_id, contract))
+				},
+			)
+		}
+
+		fn contracts_by_rentee(
+			rentee: AccountId,
+			start_after: Option<NFTId>,
+			limit: u32,
+		) -> Page<NFTId, RentContract<AccountId, BlockNumber, Balance>> {
+			runtime_apis::scan_page::<ternoa_rent::Contracts<Runtime>, _, _, _>(
+				start_after,
+				limit,
+				|nft_id, contract| {
+					(contract.rentee.as_ref() == Some(&rentee))
+						.then(|| runtime_apis::rent_contract(*nft_id, contract))
+				},
+			)
+		}
+	}
+
+	impl TeeApi<Block, AccountId, Balance> for Runtime {
+		fn clusters() -> Vec<ClusterInfo<AccountId>> {
+			ternoa_tee::ClusterData::<Runtime>::iter_keys()
+				.filter_map(runtime_apis::cluster_info)
+				.collect()
+		}
+
+		fn cluster(cluster_id: ClusterId) -> Option<ClusterInfo<AccountId>> {
+			runtime_apis::cluster_info(cluster_id)
+		}
+
+		fn enclave(operator: AccountId) -> Option<EnclaveInfo<AccountId>> {
+			ternoa_tee::EnclaveData::<Runtime>::get(&operator)
+				.map(|enclave| runtime_apis::enclave_info(operator, enclave))
+		}
+
+		fn enclave_operator(enclave_address: AccountId) -> Option<AccountId> {
+			ternoa_tee::EnclaveAccountOperator::<Runtime>::get(enclave_address)
+		}
+
+		fn pending_requests() -> PendingRequests<AccountId> {
+			runtime_apis::pending_tee_requests()
+		}
+
+		fn latest_metrics_reports(operator: AccountId) -> Vec<MetricsReport<AccountId>> {
+			runtime_apis::latest_metrics_reports(operator)
+		}
+
+		fn claimable_rewards(operator: AccountId) -> RewardsEstimate<Balance> {
+			runtime_apis::claimable_tee_rewards(operator)
+		}
+
+		fn secret_nft_shard_holders(nft_id: NFTId) -> Vec<ShardHolder<AccountId>> {
+			let addresses = ternoa_nft::SecretNftsShardsCount::<Runtime>::get(nft_id)
+				.map(|shards| shards.into_inner())
+				.unwrap_or_default();
+			runtime_apis::shard_holders(addresses)
+		}
+
+		fn capsule_shard_holders(nft_id: NFTId) -> Vec<ShardHolder<AccountId>> {
+			let addresses = ternoa_nft::CapsulesShardsCount::<Runtime>::get(nft_id)
+				.map(|shards| shards.into_inner())
+				.unwrap_or_default();
+			runtime_apis::shard_holders(addresses)
+		}
+	}
+
+	impl pallet_staking_runtime_api::StakingApi<Block, Balance> for Runtime {
+		fn nominations_quota(balance: Balance) -> u32 {
+			Staking::api_nominations_quota(balance)
+		}
+	}
+
+	impl StakingRewardsApi<Block, Balance> for Runtime {
+		fn active_era_reward_estimate() -> Option<EraRewardEstimate<Balance>> {
+			runtime_apis::active_era_reward_estimate()
+		}
+
+		fn era_validator_reward(era: EraIndex) -> Option<Balance> {
+			Staking::eras_validator_reward(era)
+		}
+	}
+
+	impl TransmissionProtocolsApi<Block, AccountId, BlockNumber> for Runtime {
+		fn transmission(nft_id: NFTId) -> Option<PendingTransmission<AccountId, BlockNumber>> {
+			TransmissionProtocols::transmissions(nft_id)
+				.and_then(|transmission| runtime_apis::pending_transmission(nft_id, transmission))
+		}
+
+		fn transmissions_by_recipient(
+			recipient: AccountId,
+			start_after: Option<NFTId>,
+			limit: u32,
+		) -> Page<NFTId, PendingTransmission<AccountId, BlockNumber>> {
+			type Transmissions = ternoa_transmission_protocols::Transmissions<Runtime>;
+			runtime_apis::scan_page::<Transmissions, _, _, _>(
+				start_after,
+				limit,
+				|nft_id, transmission| {
+					(transmission.recipient == recipient)
+						.then(|| runtime_apis::pending_transmission(*nft_id, transmission))
+						.flatten()
+				},
+			)
+		}
+	}
+
+	impl BridgeApi<Block, AccountId, BlockNumber, Balance> for Runtime {
+		fn bridge_status() -> BridgeStatus<AccountId, BlockNumber, Balance> {
+			BridgeStatus {
+				bridge_fee: Bridge::bridge_fee(),
+				relayers: Bridge::relayers().into_inner(),
+				relayer_vote_threshold: Bridge::relayer_vote_threshold(),
+				proposal_lifetime: pallets::ProposalLifetime::get(),
+				chains: ternoa_bridge::ChainNonces::<Runtime>::iter()
+					.map(|(chain_id, nonce)| runtime_apis::bridge_chain_status(chain_id, nonce))
+					.collect(),
+			}
+		}
+
+		fn chain_status(chain_id: ChainId) -> Option<ChainStatus<AccountId, BlockNumber, Balance>> {
+			Bridge::chain_nonces(chain_id)
+				.map(|nonce| runtime_apis::bridge_chain_status(chain_id, nonce))
+		}
+	}
+
+	impl DryRunApi<Block> for Runtime {
+		fn event_count() -> u32 {
+			System::event_count()
+		}
+
+		fn applied_extrinsic(event_count: u32) -> AppliedExtrinsic {
+			runtime_apis::applied_extrinsic(event_count)
+		}
+	}
+
+	impl FeesApi<Block, AccountId, Balance> for Runtime {
+		fn cost_estimate(encoded_call: Vec<u8>) -> Option<CostEstimate<AccountId, Balance>> {
+			runtime_apis::cost_estimate(encoded_call)
+		}
+	}
+
+	impl MetricsApi<Block> for Runtime {
+		fn chain_metrics() -> ChainMetrics {
+			runtime_apis::chain_metrics()
+		}
+	}
+
+	impl sp_statement_store::runtime_api::ValidateStatement<Block> for Runtime {
+		fn validate_statement(
+			_source: sp_statement_store::runtime_api::StatementSource,
+			statement: sp_statement_store::Statement,
+		) -> Result<
+			sp_statement_store::runtime_api::ValidStatement,
+			sp_statement_store::runtime_api::InvalidStatement,
+		> {
+			runtime_apis::validate_statement(statement)
+		}
+	}
+
 	impl sp_session::SessionKeys<Block> for Runtime {
 		fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
 			SessionKeys::generate(seed)
@@ -473,6 +758,9 @@ impl_runtime_apis! {
 			// have a backtrace here. If any of the pre/post migration checks fail, we shall stop
 			// right here and right now.
 			let weight = Executive::try_runtime_upgrade(checks).unwrap();
+			if checks.try_state() {
+				try_state::do_try_state().unwrap();
+			}
 			(weight, RuntimeBlockWeights::get().max_block)
 		}
 
@@ -484,7 +772,14 @@ impl_runtime_apis! {
 		) -> Weight {
 			// NOTE: intentional unwrap: we don't want to propagate the error backwards, and want to
 			// have a backtrace here.
-			Executive::try_execute_block(block, state_root_check, signature_check, select).unwrap()
+			let check_ternoa_state = try_state::is_selected(&select);
+			let weight =
+				Executive::try_execute_block(block, state_root_check, signature_check, select)
+					.unwrap();
+			if check_ternoa_state {
+				try_state::do_try_state().unwrap();
+			}
+			weight
 		}
 	}
 
@@ -588,12 +883,57 @@ mod benches {
 mod tests {
 	use super::*;
 	use frame_election_provider_support::NposSolution;
-	use frame_support::traits::WhitelistedStorageKeys;
+	use frame_support::traits::{GenesisBuild, WhitelistedStorageKeys};
 	use frame_system::offchain::CreateSignedTransaction;
 	use sp_core::hexdisplay::HexDisplay;
-	use sp_runtime::UpperOf;
+	use sp_runtime::{traits::Dispatchable, BoundedVec, Permill, UpperOf};
 	use std::collections::HashSet;
-	use ternoa_runtime_common::staking::MaxNominations;
+	use ternoa_runtime_api::MAX_PAGE_SIZE;
+	use ternoa_runtime_common::{
+		constants::{currency::UNITS, time::MILLISECS_PER_BLOCK},
+		staking::{MaxNominations, SessionsPerEra},
+	};
+
+	fn alice() -> AccountId {
+		AccountId::from([1; 32])
+	}
+
+	fn bob() -> AccountId {
+		AccountId::from([2; 32])
+	}
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let mut storage =
+			frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(alice(), 1_000_000 * UNITS), (bob(), 1_000_000 * UNITS)],
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(storage);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	fn mint_nft(owner: &AccountId) -> NFTId {
+		let call = RuntimeCall::NFT(ternoa_nft::Call::create_nft {
+			offchain_data: BoundedVec::try_from(b"offchain data".to_vec()).unwrap(),
+			royalty: Permill::from_percent(10),
+			collection_id: None,
+			is_soulbound: false,
+		});
+		call.dispatch(RuntimeOrigin::signed(owner.clone())).unwrap();
+		ternoa_nft::NextNFTId::<Runtime>::get() - 1
+	}
+
+	fn create_marketplace(owner: &AccountId) -> MarketplaceId {
+		let kind = ternoa_pallets_primitives::marketplace::MarketplaceType::Public;
+		RuntimeCall::Marketplace(ternoa_marketplace::Call::create_marketplace { kind })
+			.dispatch(RuntimeOrigin::signed(owner.clone()))
+			.unwrap();
+		ternoa_marketplace::NextMarketplaceId::<Runtime>::get() - 1
+	}
 
 	#[test]
 	fn check_whitelist() {
@@ -650,6 +990,383 @@ mod tests {
 			maximum_chain_accuracy.iter().fold(0, |acc, x| acc.checked_add(*x).unwrap());
 	}
 
+	#[test]
+	fn nfts_by_owner_pages_over_all_nfts() {
+		new_test_ext().execute_with(|| {
+			let alice_nft = mint_nft(&alice());
+			let bob_nft = mint_nft(&bob());
+			let last_alice_nft = mint_nft(&alice());
+
+			let details = runtime_apis::nft_details(alice_nft, NFT::nfts(alice_nft).unwrap());
+			assert_eq!(details.owner, alice());
+			assert_eq!(details.creator, alice());
+			assert_eq!(details.offchain_data, b"offchain data".to_vec());
+			assert_eq!(details.royalty, Permill::from_percent(10));
+			assert!(!details.state.is_listed);
+
+			let owned_by = |owner: AccountId, start_after, limit| {
+				runtime_apis::scan_page::<ternoa_nft::Nfts<Runtime>, _, _, _>(
+					start_after,
+					limit,
+					|nft_id, data| (data.owner == owner).then_some(*nft_id),
+				)
+			};
+
+			// The limit counts scanned NFTs, so a page can hold fewer items than the limit.
+			let first = owned_by(alice(), None, 2);
+			assert!(first.items.len() <= 2);
+			let next = first.next.expect("one NFT is left to scan");
+			let second = owned_by(alice(), Some(next), 2);
+			assert_eq!(second.next, None);
+
+			let mut owned: Vec<NFTId> = first.items.into_iter().chain(second.items).collect();
+			owned.sort();
+			assert_eq!(owned, vec![alice_nft, last_alice_nft]);
+			assert_eq!(owned_by(bob(), None, MAX_PAGE_SIZE).items, vec![bob_nft]);
+		});
+	}
+
+	#[test]
+	fn buy_quote_splits_the_price_of_a_listed_nft() {
+		new_test_ext().execute_with(|| {
+			let nft_id = mint_nft(&alice());
+			assert_eq!(runtime_apis::buy_quote(nft_id), None);
+
+			let marketplace_id = create_marketplace(&alice());
+			RuntimeCall::NFT(ternoa_nft::Call::transfer_nft { nft_id, recipient: bob().into() })
+				.dispatch(RuntimeOrigin::signed(alice()))
+				.unwrap();
+			let price = 100 * UNITS;
+			RuntimeCall::Marketplace(ternoa_marketplace::Call::list_nft {
+				nft_id,
+				marketplace_id,
+				price,
+			})
+			.dispatch(RuntimeOrigin::signed(bob()))
+			.unwrap();
+
+			let quote = runtime_apis::buy_quote(nft_id).unwrap();
+			assert_eq!(quote.seller, bob());
+			assert_eq!(quote.marketplace_owner, alice());
+			assert_eq!(quote.marketplace_commission, 0);
+			assert_eq!(quote.creator, alice());
+			assert_eq!(quote.royalty, 10 * UNITS);
+			assert_eq!(quote.seller_proceeds, 90 * UNITS);
+			assert!(quote.transaction_fee > 0);
+			assert_eq!(quote.total, price + quote.transaction_fee);
+
+			type ListedNfts = ternoa_marketplace::ListedNfts<Runtime>;
+			let listings =
+				runtime_apis::scan_page::<ListedNfts, _, _, _>(None, MAX_PAGE_SIZE, |id, sale| {
+					(sale.marketplace_id == marketplace_id)
+						.then(|| runtime_apis::listing(*id, sale))
+				});
+			assert_eq!(listings.next, None);
+			assert_eq!(
+				listings.items,
+				vec![Listing { nft_id, marketplace_id, seller: bob(), price }]
+			);
+		});
+	}
+
+	#[test]
+	fn auction_state_tracks_the_ending_period() {
+		new_test_ext().execute_with(|| {
+			let nft_id = mint_nft(&alice());
+			let marketplace_id = create_marketplace(&alice());
+			RuntimeCall::Auction(ternoa_auction::Call::create_auction {
+				nft_id,
+				marketplace_id,
+				start_block: 1,
+				end_block: 1_001,
+				start_price: 10 * UNITS,
+				buy_it_price: None,
+			})
+			.dispatch(RuntimeOrigin::signed(alice()))
+			.unwrap();
+
+			let state = |now| {
+				System::set_block_number(now);
+				runtime_apis::auction_state(nft_id, Auction::auctions(nft_id).unwrap())
+			};
+
+			let auction = state(1);
+			assert_eq!(auction.creator, alice());
+			assert_eq!((auction.start_block, auction.end_block), (1, 1_001));
+			assert_eq!(auction.highest_bid, None);
+			assert!(!auction.is_in_ending_period);
+			assert!(!state(900).is_in_ending_period);
+			assert!(state(901).is_in_ending_period);
+			assert!(state(1_000).is_in_ending_period);
+			assert!(!state(1_001).is_in_ending_period);
+		});
+	}
+
+	#[test]
+	fn contracts_are_paged_by_renter_and_rentee() {
+		use ternoa_rent::types::{AcceptanceType, CancellationFee, Duration, RentFee};
+		use ternoa_runtime_api::rent::RentDuration;
+
+		new_test_ext().execute_with(|| {
+			let nft_id = mint_nft(&alice());
+			RuntimeCall::Rent(ternoa_rent::Call::create_contract {
+				nft_id,
+				duration: Duration::Fixed(1_000),
+				acceptance_type: AcceptanceType::AutoAcceptance(None),
+				renter_can_revoke: false,
+				rent_fee: RentFee::Tokens(UNITS),
+				renter_cancellation_fee: CancellationFee::None,
+				rentee_cancellation_fee: CancellationFee::None,
+			})
+			.dispatch(RuntimeOrigin::signed(alice()))
+			.unwrap();
+
+			let contract = runtime_apis::rent_contract(nft_id, Rent::contracts(nft_id).unwrap());
+			assert_eq!(contract.renter, alice());
+			assert_eq!(contract.rentee, None);
+			assert_eq!(contract.duration, RentDuration::Fixed(1_000));
+			assert_eq!(contract.next_payment, None);
+
+			type Contracts = ternoa_rent::Contracts<Runtime>;
+			let by_renter =
+				runtime_apis::scan_page::<Contracts, _, _, _>(None, 1, |id, contract| {
+					(contract.renter == alice()).then_some(*id)
+				});
+			assert_eq!(by_renter, Page { items: vec![nft_id], next: None });
+			let by_rentee =
+				runtime_apis::scan_page::<Contracts, _, _, _>(None, 1, |id, contract| {
+					(contract.rentee == Some(bob())).then_some(*id)
+				});
+			assert_eq!(by_rentee, Page { items: vec![], next: None });
+		});
+	}
+
+	#[test]
+	fn claimable_tee_rewards_skips_claimed_eras() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(runtime_apis::cluster_info(0), None);
+			let nothing = runtime_apis::claimable_tee_rewards(alice());
+			assert_eq!(nothing, RewardsEstimate { unclaimed_eras: vec![], estimated_amount: 0 });
+
+			pallet_staking::ActiveEra::<Runtime>::put(pallet_staking::ActiveEraInfo {
+				index: 5,
+				start: None,
+			});
+			ternoa_tee::OperatorAssignedEra::<Runtime>::insert(alice(), 2);
+			ternoa_tee::ClaimedRewards::<Runtime>::insert(
+				alice(),
+				BoundedVec::try_from(vec![3]).unwrap(),
+			);
+			ternoa_tee::DailyRewardPool::<Runtime>::put(100 * UNITS);
+
+			// No enclave is assigned to a cluster, so there is nothing to share the pool with.
+			let rewards = runtime_apis::claimable_tee_rewards(alice());
+			assert_eq!(rewards.unclaimed_eras, vec![2, 4]);
+			assert_eq!(rewards.estimated_amount, 0);
+			assert_eq!(runtime_apis::claimable_tee_rewards(bob()), nothing);
+		});
+	}
+
+	#[test]
+	fn era_reward_estimate_uses_the_active_era_stake() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(runtime_apis::active_era_reward_estimate(), None);
+
+			pallet_staking::ActiveEra::<Runtime>::put(pallet_staking::ActiveEraInfo {
+				index: 3,
+				start: None,
+			});
+			pallet_staking::ErasTotalStake::<Runtime>::insert(3, 500_000 * UNITS);
+
+			let estimate = runtime_apis::active_era_reward_estimate().unwrap();
+			assert_eq!(estimate.era, 3);
+			assert_eq!(estimate.total_staked, 500_000 * UNITS);
+			assert_eq!(estimate.total_issuance, 2_000_000 * UNITS);
+			assert_eq!(
+				estimate.era_duration_millis,
+				SessionsPerEra::get() as u64 * EpochDuration::get() * MILLISECS_PER_BLOCK
+			);
+		});
+	}
+
+	#[test]
+	fn transmissions_are_paged_by_recipient() {
+		use ternoa_runtime_api::transmission_protocols::ProtocolKind;
+		use ternoa_transmission_protocols::types::{CancellationPeriod, TransmissionProtocol};
+
+		new_test_ext().execute_with(|| {
+			let nft_id = mint_nft(&alice());
+			RuntimeCall::TransmissionProtocols(
+				ternoa_transmission_protocols::Call::set_transmission_protocol {
+					nft_id,
+					recipient: bob(),
+					protocol: TransmissionProtocol::AtBlock(100),
+					protocol_cancellation: CancellationPeriod::None,
+				},
+			)
+			.dispatch(RuntimeOrigin::signed(alice()))
+			.unwrap();
+
+			let transmission = TransmissionProtocols::transmissions(nft_id).unwrap();
+			let pending = runtime_apis::pending_transmission(nft_id, transmission).unwrap();
+			assert_eq!(pending.owner, alice());
+			assert_eq!(pending.recipient, bob());
+			assert_eq!(pending.kind, ProtocolKind::AtBlock);
+			assert_eq!(pending.trigger_block, Some(100));
+			assert_eq!(pending.threshold, None);
+
+			type Transmissions = ternoa_transmission_protocols::Transmissions<Runtime>;
+			let to = |recipient: AccountId| {
+				runtime_apis::scan_page::<Transmissions, _, _, _>(None, 1, |id, transmission| {
+					(transmission.recipient == recipient).then_some(*id)
+				})
+			};
+			assert_eq!(to(bob()), Page { items: vec![nft_id], next: None });
+			assert_eq!(to(alice()), Page { items: vec![], next: None });
+		});
+	}
+
+	#[test]
+	fn bridge_chain_status_lists_the_open_proposals() {
+		use ternoa_bridge::types::{Proposal, ProposalStatus};
+		use ternoa_runtime_api::bridge::OpenProposal;
+
+		new_test_ext().execute_with(|| {
+			let proposal = |status| Proposal {
+				votes: BoundedVec::try_from(vec![alice()]).unwrap(),
+				status,
+				expiry: 51,
+			};
+			ternoa_bridge::Votes::<Runtime>::insert(
+				1,
+				(7, bob(), UNITS),
+				proposal(ProposalStatus::Initiated),
+			);
+			ternoa_bridge::Votes::<Runtime>::insert(
+				1,
+				(6, bob(), UNITS),
+				proposal(ProposalStatus::Approved),
+			);
+			ternoa_bridge::Votes::<Runtime>::insert(
+				2,
+				(7, bob(), UNITS),
+				proposal(ProposalStatus::Initiated),
+			);
+
+			let status = runtime_apis::bridge_chain_status(1, 7);
+			assert_eq!((status.chain_id, status.deposit_nonce), (1, 7));
+			assert_eq!(
+				status.open_proposals,
+				vec![OpenProposal {
+					deposit_nonce: 7,
+					recipient: bob(),
+					amount: UNITS,
+					voters: vec![alice()],
+					expiry: 51,
+					remaining_lifetime: 50,
+				}]
+			);
+			assert!(runtime_apis::bridge_chain_status(3, 0).open_proposals.is_empty());
+		});
+	}
+
+	#[test]
+	fn applied_extrinsic_keeps_the_events_after_the_count() {
+		use frame_support::{dispatch::DispatchInfo, weights::Weight};
+		use parity_scale_codec::Encode;
+
+		new_test_ext().execute_with(|| {
+			System::deposit_event(frame_system::Event::NewAccount { account: alice() });
+			let event_count = System::event_count();
+			assert_eq!(runtime_apis::applied_extrinsic(event_count).actual_weight, None);
+
+			let new_account =
+				RuntimeEvent::System(frame_system::Event::NewAccount { account: bob() });
+			let dispatch_info =
+				DispatchInfo { weight: Weight::from_parts(1_000, 0), ..Default::default() };
+			let success =
+				RuntimeEvent::System(frame_system::Event::ExtrinsicSuccess { dispatch_info });
+			System::deposit_event(new_account.clone());
+			System::deposit_event(success.clone());
+
+			let applied = runtime_apis::applied_extrinsic(event_count);
+			assert_eq!(applied.events, vec![new_account.encode(), success.encode()]);
+			assert_eq!(applied.actual_weight, Some(Weight::from_parts(1_000, 0)));
+		});
+	}
+
+	#[test]
+	fn cost_estimate_adds_the_protocol_fees() {
+		use parity_scale_codec::Encode;
+		use ternoa_runtime_api::fees::{Charge, ChargeDestination, ChargeReason};
+
+		new_test_ext().execute_with(|| {
+			RuntimeCall::NFT(ternoa_nft::Call::set_nft_mint_fee { fee: 10 * UNITS })
+				.dispatch(RuntimeOrigin::root())
+				.unwrap();
+			let create_nft = RuntimeCall::NFT(ternoa_nft::Call::create_nft {
+				offchain_data: BoundedVec::try_from(b"offchain data".to_vec()).unwrap(),
+				royalty: Permill::zero(),
+				collection_id: None,
+				is_soulbound: false,
+			});
+			let transaction_fee = runtime_apis::estimate_call_fee(create_nft.clone());
+			assert!(transaction_fee > 0);
+
+			let estimate = runtime_apis::cost_estimate(create_nft.encode()).unwrap();
+			assert_eq!(
+				estimate.charges,
+				vec![
+					Charge {
+						destination: ChargeDestination::TransactionFees,
+						reason: ChargeReason::TransactionFee,
+						amount: transaction_fee,
+					},
+					Charge {
+						destination: ChargeDestination::Treasury,
+						reason: ChargeReason::NftMintFee,
+						amount: 10 * UNITS,
+					},
+				]
+			);
+			assert_eq!(estimate.total, transaction_fee + 10 * UNITS);
+
+			let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+			assert_eq!(runtime_apis::cost_estimate(remark.encode()).unwrap().charges.len(), 1);
+			assert_eq!(runtime_apis::cost_estimate(vec![0xff]), None);
+		});
+	}
+
+	#[test]
+	#[cfg(feature = "try-runtime")]
+	fn try_state_checks_the_marketplace_listings() {
+		use frame_try_runtime::TryStateSelect;
+
+		assert!(try_state::is_selected(&TryStateSelect::All));
+		assert!(try_state::is_selected(&TryStateSelect::RoundRobin(1)));
+		assert!(try_state::is_selected(&TryStateSelect::Only(vec![b"Marketplace".to_vec()])));
+		assert!(!try_state::is_selected(&TryStateSelect::Only(vec![b"Balances".to_vec()])));
+		assert!(!try_state::is_selected(&TryStateSelect::None));
+
+		new_test_ext().execute_with(|| {
+			let nft_id = mint_nft(&alice());
+			let marketplace_id = create_marketplace(&alice());
+			RuntimeCall::Marketplace(ternoa_marketplace::Call::list_nft {
+				nft_id,
+				marketplace_id,
+				price: UNITS,
+			})
+			.dispatch(RuntimeOrigin::signed(alice()))
+			.unwrap();
+			assert_eq!(try_state::do_try_state(), Ok(()));
+
+			ternoa_nft::Nfts::<Runtime>::mutate(nft_id, |nft| {
+				nft.as_mut().unwrap().state.listed_for_sale = false;
+			});
+			assert_eq!(try_state::do_try_state(), Err("Listed NFT is not marked as listed"));
+		});
+	}
+
 	#[test]
 	fn call_size() {
 		let size = core::mem::size_of::<RuntimeCall>();
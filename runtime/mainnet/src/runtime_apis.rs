@@ -0,0 +1,710 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Conversions from pallet storage to the views returned by the Ternoa runtime APIs.
+
+use crate::{
+	pallets::EpochDuration, Balances, Bridge, Marketplace, Rent, Runtime, RuntimeCall,
+	RuntimeEvent, Staking, System, TransactionPayment, TransmissionProtocols, NFT, TEE,
+};
+use frame_support::{
+	dispatch::GetDispatchInfo,
+	storage::IterableStorageMap,
+	traits::{Currency, Get},
+};
+use parity_scale_codec::{DecodeLimit, Encode, FullCodec};
+use sp_statement_store::{
+	runtime_api::{InvalidStatement, ValidStatement},
+	SignatureVerificationResult, Statement,
+};
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
+use ternoa_core_primitives::{AccountId, Balance, BlockNumber, EraIndex};
+use ternoa_pallets_primitives::marketplace::CompoundFee;
+use ternoa_runtime_api::{
+	auction::AuctionState,
+	bridge::{ChainId, ChainStatus, DepositNonce, OpenProposal},
+	dry_run::AppliedExtrinsic,
+	fees::{Charge, ChargeDestination, ChargeReason, CostEstimate},
+	marketplace::{BuyQuote, Listing, MarketplaceId},
+	metrics::ChainMetrics,
+	nft::{CollectionDetails, CollectionId, NFTId, NftDetails, NftState},
+	rent::{RentContract, RentDuration, RentFee, SubscriptionPayment},
+	staking_rewards::EraRewardEstimate,
+	statement::nft_from_topic,
+	tee::{
+		ClusterId, ClusterInfo, EnclaveInfo, MetricsReport, PendingRequests, RewardsEstimate,
+		ShardHolder,
+	},
+	transmission_protocols::{Consent, PendingTransmission, ProtocolKind},
+	Page, MAX_PAGE_SIZE,
+};
+
+/// Approximate size of the address, signature and signed extensions of a signed extrinsic, used
+/// to estimate length fees of calls that are not submitted yet.
+const SIGNED_EXTRINSIC_OVERHEAD: u32 = 110;
+
+/// Number and total size of the statements that a TEE enclave or its operator can keep in the
+/// statement store of a node.
+const ENCLAVE_STATEMENTS: (u32, u32) = (1_000, 1024 * 1024);
+
+/// Number and total size of the statements that an NFT owner can keep in the statement store of
+/// a node.
+const NFT_OWNER_STATEMENTS: (u32, u32) = (16, 16 * 1024);
+
+type NFTData = ternoa_pallets_primitives::nfts::NFTData<
+	AccountId,
+	<Runtime as ternoa_nft::Config>::NFTOffchainDataLimit,
+>;
+type Collection = ternoa_pallets_primitives::nfts::Collection<
+	AccountId,
+	<Runtime as ternoa_nft::Config>::CollectionOffchainDataLimit,
+	<Runtime as ternoa_nft::Config>::CollectionSizeLimit,
+>;
+type Sale = ternoa_pallets_primitives::marketplace::Sale<AccountId, Balance>;
+type AuctionData = ternoa_auction::types::AuctionData<
+	AccountId,
+	BlockNumber,
+	Balance,
+	<Runtime as ternoa_auction::Config>::BidderListLengthLimit,
+>;
+type RentContractData = ternoa_rent::types::RentContractData<
+	AccountId,
+	BlockNumber,
+	Balance,
+	<Runtime as ternoa_rent::Config>::AccountSizeLimit,
+>;
+type TransmissionData = ternoa_transmission_protocols::types::TransmissionData<
+	AccountId,
+	BlockNumber,
+	<Runtime as ternoa_transmission_protocols::Config>::MaxConsentListSize,
+>;
+type Enclave = ternoa_tee::Enclave<AccountId, <Runtime as ternoa_tee::Config>::MaxUriLen>;
+
+/// Scans at most `limit` entries of `Map` after the key `start_after` and keeps the ones that `f`
+/// turns into an item.
+pub fn scan_page<Map, K, V, T>(
+	start_after: Option<K>,
+	limit: u32,
+	mut f: impl FnMut(&K, V) -> Option<T>,
+) -> Page<K, T>
+where
+	Map: IterableStorageMap<K, V>,
+	K: FullCodec,
+	V: FullCodec,
+{
+	let mut entries = match start_after {
+		Some(key) => Map::iter_from(Map::hashed_key_for(key)),
+		None => Map::iter(),
+	}
+	.peekable();
+	let mut items = Vec::new();
+	let mut last = None;
+	for (key, value) in entries.by_ref().take(limit.min(MAX_PAGE_SIZE) as usize) {
+		items.extend(f(&key, value));
+		last = Some(key);
+	}
+
+	Page { items, next: if entries.peek().is_some() { last } else { None } }
+}
+
+pub fn nft_state(data: &NFTData) -> NftState {
+	NftState {
+		is_capsule: data.state.is_capsule,
+		is_secret: data.state.is_secret,
+		is_soulbound: data.state.is_soulbound,
+		is_listed: data.state.listed_for_sale,
+		is_rented: data.state.is_rented,
+		is_delegated: data.state.is_delegated,
+		is_in_transmission: data.state.is_transmission,
+	}
+}
+
+pub fn nft_details(nft_id: NFTId, data: NFTData) -> NftDetails<AccountId> {
+	let state = nft_state(&data);
+	let delegated_to = if state.is_delegated { NFT::delegated_nfts(nft_id) } else { None };
+
+	NftDetails {
+		nft_id,
+		owner: data.owner,
+		creator: data.creator,
+		offchain_data: data.offchain_data.into_inner(),
+		royalty: data.royalty,
+		collection_id: data.collection_id,
+		delegated_to,
+		state,
+	}
+}
+
+pub fn collection_details(
+	collection_id: CollectionId,
+	collection: Collection,
+) -> CollectionDetails<AccountId> {
+	CollectionDetails {
+		collection_id,
+		owner: collection.owner,
+		offchain_data: collection.offchain_data.into_inner(),
+		nfts: collection.nfts.into_inner(),
+		limit: collection.limit,
+		is_closed: collection.is_closed,
+	}
+}
+
+pub fn listing(nft_id: NFTId, sale: Sale) -> Listing<AccountId, Balance> {
+	Listing {
+		nft_id,
+		marketplace_id: sale.marketplace_id,
+		seller: sale.account_id,
+		price: sale.price,
+	}
+}
+
+/// Estimates the fee of an unsigned call as if it was submitted as a signed extrinsic.
+pub fn estimate_call_fee(call: RuntimeCall) -> Balance {
+	let len = call.encoded_size() as u32 + SIGNED_EXTRINSIC_OVERHEAD;
+	TransactionPayment::compute_fee(len, &call.get_dispatch_info(), 0)
+}
+
+/// Counts the state of the Ternoa pallets for the node metrics.
+pub fn chain_metrics() -> ChainMetrics {
+	use ternoa_bridge::types::ProposalStatus;
+
+	let (nfts, secret_nfts, capsules) = ternoa_nft::Nfts::<Runtime>::iter_values().fold(
+		(0u32, 0u32, 0u32),
+		|(nfts, secret_nfts, capsules), nft| {
+			(
+				nfts + 1,
+				secret_nfts + nft.state.is_secret as u32,
+				capsules + nft.state.is_capsule as u32,
+			)
+		},
+	);
+
+	let mut listings = BTreeMap::<MarketplaceId, u32>::new();
+	for sale in ternoa_marketplace::ListedNfts::<Runtime>::iter_values() {
+		*listings.entry(sale.marketplace_id).or_default() += 1;
+	}
+
+	let cluster_size = <Runtime as ternoa_tee::Config>::ClusterSize::get();
+
+	ChainMetrics {
+		nfts,
+		collections: ternoa_nft::Collections::<Runtime>::iter_keys().count() as u32,
+		secret_nfts,
+		capsules,
+		listings: listings.into_iter().collect(),
+		auctions: ternoa_auction::Auctions::<Runtime>::iter_keys().count() as u32,
+		rent_contracts: ternoa_rent::Contracts::<Runtime>::iter_keys().count() as u32,
+		pending_transmissions: ternoa_transmission_protocols::Transmissions::<Runtime>::iter_keys()
+			.count() as u32,
+		clusters_with_free_slots: ternoa_tee::ClusterData::<Runtime>::iter_values()
+			.filter(|cluster| (cluster.enclaves.len() as u32) < cluster_size)
+			.count() as u32,
+		pending_bridge_proposals: ternoa_bridge::Votes::<Runtime>::iter_values()
+			.filter(|proposal| proposal.status == ProposalStatus::Initiated)
+			.count() as u32,
+	}
+}
+
+/// Splits the price of a listed NFT the same way `buy_nft` does: the commission locked at listing
+/// time goes to the marketplace owner, the royalty on what remains goes to the creator (unless the
+/// creator is the seller) and the rest goes to the seller.
+pub fn buy_quote(nft_id: NFTId) -> Option<BuyQuote<AccountId, Balance>> {
+	let sale = Marketplace::listed_nfts(nft_id)?;
+	let nft = NFT::nfts(nft_id)?;
+	let marketplace = Marketplace::marketplaces(sale.marketplace_id)?;
+
+	let price = sale.price;
+	let marketplace_commission = match sale.commission_fee {
+		Some(CompoundFee::Flat(fee)) => fee.min(price),
+		Some(CompoundFee::Percentage(fee)) => fee * price,
+		None => 0,
+	};
+	let royalty = if nft.creator != sale.account_id {
+		nft.royalty * price.saturating_sub(marketplace_commission)
+	} else {
+		0
+	};
+	let seller_proceeds = price.saturating_sub(marketplace_commission).saturating_sub(royalty);
+
+	let call =
+		RuntimeCall::Marketplace(ternoa_marketplace::Call::buy_nft { nft_id, signed_price: price });
+	let transaction_fee = estimate_call_fee(call);
+
+	Some(BuyQuote {
+		price,
+		seller: sale.account_id,
+		seller_proceeds,
+		marketplace_owner: marketplace.owner,
+		marketplace_commission,
+		creator: nft.creator,
+		royalty,
+		transaction_fee,
+		total: price.saturating_add(transaction_fee),
+	})
+}
+
+pub fn auction_state(
+	nft_id: NFTId,
+	auction: AuctionData,
+) -> AuctionState<AccountId, BlockNumber, Balance> {
+	let ending_period = <Runtime as ternoa_auction::Config>::AuctionEndingPeriod::get();
+	let now = System::block_number();
+	let is_in_ending_period =
+		now >= auction.end_block.saturating_sub(ending_period) && now < auction.end_block;
+	let bidders = auction.bidders.list.into_inner();
+
+	AuctionState {
+		nft_id,
+		creator: auction.creator,
+		marketplace_id: auction.marketplace_id,
+		start_block: auction.start_block,
+		end_block: auction.end_block,
+		is_extended: auction.is_extended,
+		is_in_ending_period,
+		start_price: auction.start_price,
+		buy_it_price: auction.buy_it_price,
+		highest_bid: bidders.last().cloned(),
+		bidders,
+	}
+}
+
+pub fn rent_contract(
+	nft_id: NFTId,
+	contract: RentContractData,
+) -> RentContract<AccountId, BlockNumber, Balance> {
+	let rent_fee = match contract.rent_fee {
+		ternoa_rent::types::RentFee::Tokens(amount) => RentFee::Tokens(amount),
+		ternoa_rent::types::RentFee::NFT(fee_nft_id) => RentFee::Nft(fee_nft_id),
+	};
+	let duration = match contract.duration {
+		ternoa_rent::types::Duration::Fixed(blocks) => RentDuration::Fixed(blocks),
+		ternoa_rent::types::Duration::Subscription(period, ..) =>
+			RentDuration::Subscription(period),
+	};
+	let next_payment = match duration {
+		RentDuration::Subscription(_) if contract.start_block.is_some() => Rent::queues()
+			.subscription_queue
+			.get(nft_id)
+			.map(|block| SubscriptionPayment { block, amount: rent_fee.clone() }),
+		_ => None,
+	};
+	let offers = Rent::offers(nft_id).map(|offers| offers.into_inner()).unwrap_or_default();
+
+	RentContract {
+		nft_id,
+		renter: contract.renter,
+		rentee: contract.rentee,
+		start_block: contract.start_block,
+		duration,
+		rent_fee,
+		renter_can_revoke: contract.renter_can_revoke,
+		offers,
+		next_payment,
+	}
+}
+
+pub fn enclave_info(operator: AccountId, enclave: Enclave) -> EnclaveInfo<AccountId> {
+	let assignment =
+		ternoa_tee::EnclaveClusterId::<Runtime>::get(&operator).and_then(|cluster_id| {
+			let cluster = ternoa_tee::ClusterData::<Runtime>::get(cluster_id)?;
+			let slot_id = cluster.enclaves.iter().find(|(o, _)| *o == operator).map(|(_, s)| *s);
+			Some((cluster_id, slot_id))
+		});
+
+	EnclaveInfo {
+		operator,
+		enclave_address: enclave.enclave_address,
+		api_uri: enclave.api_uri.into_inner(),
+		cluster_id: assignment.map(|(cluster_id, _)| cluster_id),
+		slot_id: assignment.and_then(|(_, slot_id)| slot_id),
+	}
+}
+
+pub fn cluster_info(cluster_id: ClusterId) -> Option<ClusterInfo<AccountId>> {
+	let cluster = ternoa_tee::ClusterData::<Runtime>::get(cluster_id)?;
+	let enclaves = cluster.enclaves.into_inner();
+	let size = <Runtime as ternoa_tee::Config>::ClusterSize::get();
+
+	Some(ClusterInfo {
+		cluster_id,
+		free_slots: size.saturating_sub(enclaves.len() as u32),
+		enclaves,
+	})
+}
+
+pub fn pending_tee_requests() -> PendingRequests<AccountId> {
+	PendingRequests {
+		registrations: ternoa_tee::EnclaveRegistrations::<Runtime>::iter()
+			.map(|(operator, enclave)| enclave_info(operator, enclave))
+			.collect(),
+		updates: ternoa_tee::EnclaveUpdates::<Runtime>::iter()
+			.map(|(operator, enclave)| enclave_info(operator, enclave))
+			.collect(),
+		unregistrations: ternoa_tee::EnclaveUnregistrations::<Runtime>::get().into_inner(),
+	}
+}
+
+fn active_era() -> EraIndex {
+	Staking::active_era().map(|era| era.index).unwrap_or_default()
+}
+
+pub fn latest_metrics_reports(operator: AccountId) -> Vec<MetricsReport<AccountId>> {
+	let era = active_era();
+	(era.saturating_sub(1)..=era)
+		.flat_map(|era| {
+			ternoa_tee::MetricsReports::<Runtime>::get(era, &operator)
+				.map(|reports| reports.into_inner())
+				.unwrap_or_default()
+				.into_iter()
+				.map(move |report| MetricsReport {
+					era,
+					submitted_by: report.submitted_by,
+					params: vec![
+						report.param_1,
+						report.param_2,
+						report.param_3,
+						report.param_4,
+						report.param_5,
+					],
+				})
+		})
+		.collect()
+}
+
+/// Lists the eras `operator` can still claim and assumes every assigned enclave gets an equal
+/// share of the daily reward pool for each of them. The assigned enclaves are counted from the
+/// clusters, which are few, rather than by iterating over every operator.
+pub fn claimable_tee_rewards(operator: AccountId) -> RewardsEstimate<Balance> {
+	let active_era = active_era();
+	let history_depth = <Runtime as ternoa_tee::Config>::TeeHistoryDepth::get();
+	let Some(assigned_era) = ternoa_tee::OperatorAssignedEra::<Runtime>::get(&operator) else {
+		return RewardsEstimate { unclaimed_eras: Vec::new(), estimated_amount: 0 }
+	};
+	let claimed = ternoa_tee::ClaimedRewards::<Runtime>::get(&operator)
+		.map(|eras| eras.into_inner())
+		.unwrap_or_default();
+	let unclaimed_eras: Vec<EraIndex> =
+		(assigned_era.max(active_era.saturating_sub(history_depth))..active_era)
+			.filter(|era| !claimed.contains(era))
+			.collect();
+
+	let assigned_enclaves: Balance = ternoa_tee::ClusterData::<Runtime>::iter_values()
+		.map(|cluster| cluster.enclaves.len() as Balance)
+		.sum();
+	let share = ternoa_tee::DailyRewardPool::<Runtime>::get()
+		.checked_div(assigned_enclaves)
+		.unwrap_or_default();
+
+	RewardsEstimate {
+		estimated_amount: share.saturating_mul(unclaimed_eras.len() as Balance),
+		unclaimed_eras,
+	}
+}
+
+pub fn shard_holders(enclave_addresses: Vec<AccountId>) -> Vec<ShardHolder<AccountId>> {
+	enclave_addresses
+		.into_iter()
+		.map(|enclave_address| {
+			let operator = ternoa_tee::EnclaveAccountOperator::<Runtime>::get(&enclave_address);
+			let cluster_id = operator
+				.as_ref()
+				.and_then(|operator| ternoa_tee::EnclaveClusterId::<Runtime>::get(operator));
+			ShardHolder { enclave_address, operator, cluster_id }
+		})
+		.collect()
+}
+
+/// Runs `ternoa_staking_rewards`' `EraPayout` on the stake of the active era and its nominal
+/// duration.
+pub fn active_era_reward_estimate() -> Option<EraRewardEstimate<Balance>> {
+	use pallet_staking::EraPayout;
+
+	let era = Staking::active_era()?.index;
+	let total_staked = Staking::eras_total_stake(era);
+	let total_issuance = Balances::total_issuance();
+	let era_duration_millis = <Runtime as pallet_staking::Config>::SessionsPerEra::get() as u64 *
+		EpochDuration::get() *
+		<Runtime as pallet_babe::Config>::ExpectedBlockTime::get();
+	let data = ternoa_staking_rewards::Data::<Runtime>::get();
+	let (validator_payout, remainder) = <Runtime as pallet_staking::Config>::EraPayout::era_payout(
+		total_staked,
+		total_issuance,
+		era_duration_millis,
+	);
+
+	Some(EraRewardEstimate {
+		era,
+		total_staked,
+		total_issuance,
+		era_duration_millis,
+		session_era_payout: data.session_era_payout,
+		session_extra_reward_payout: data.session_extra_reward_payout,
+		validator_payout,
+		remainder,
+	})
+}
+
+/// Returns `None` if the NFT no longer exists, which can only happen while the transmission is
+/// being removed.
+pub fn pending_transmission(
+	nft_id: NFTId,
+	transmission: TransmissionData,
+) -> Option<PendingTransmission<AccountId, BlockNumber>> {
+	use ternoa_transmission_protocols::types::TransmissionProtocol;
+
+	let owner = NFT::nfts(nft_id)?.owner;
+	let (kind, block, consent_list, threshold) = match transmission.protocol {
+		TransmissionProtocol::AtBlock(block) => (ProtocolKind::AtBlock, Some(block), None, None),
+		TransmissionProtocol::AtBlockWithReset(block) =>
+			(ProtocolKind::AtBlockWithReset, Some(block), None, None),
+		TransmissionProtocol::OnConsent { consent_list, threshold } =>
+			(ProtocolKind::OnConsent, None, Some(consent_list), Some(threshold)),
+		TransmissionProtocol::OnConsentAtBlock { consent_list, threshold, block } =>
+			(ProtocolKind::OnConsentAtBlock, Some(block), Some(consent_list), Some(threshold)),
+	};
+	// The queue holds the block the transmission is actually scheduled for.
+	let trigger_block =
+		block.map(|block| TransmissionProtocols::at_block_queue().get(nft_id).unwrap_or(block));
+	let consented = TransmissionProtocols::on_consent_data(nft_id)
+		.map(|accounts| accounts.into_inner())
+		.unwrap_or_default();
+	let consent_list = consent_list
+		.map(|list| list.into_inner())
+		.unwrap_or_default()
+		.into_iter()
+		.map(|account| Consent { has_consented: consented.contains(&account), account })
+		.collect();
+
+	Some(PendingTransmission {
+		nft_id,
+		owner,
+		recipient: transmission.recipient,
+		kind,
+		trigger_block,
+		consent_list,
+		threshold,
+	})
+}
+
+/// Lists the proposals of `chain_id` that are still waiting for votes.
+pub fn bridge_chain_status(
+	chain_id: ChainId,
+	deposit_nonce: DepositNonce,
+) -> ChainStatus<AccountId, BlockNumber, Balance> {
+	use ternoa_bridge::types::ProposalStatus;
+
+	let now = System::block_number();
+	let open_proposals = ternoa_bridge::Votes::<Runtime>::iter_prefix(chain_id)
+		.filter(|(_, proposal)| proposal.status == ProposalStatus::Initiated)
+		.map(|((deposit_nonce, recipient, amount), proposal)| OpenProposal {
+			deposit_nonce,
+			recipient,
+			amount,
+			voters: proposal.votes.into_inner(),
+			expiry: proposal.expiry,
+			remaining_lifetime: proposal.expiry.saturating_sub(now),
+		})
+		.collect();
+
+	ChainStatus { chain_id, deposit_nonce, open_proposals }
+}
+
+pub fn applied_extrinsic(event_count: u32) -> AppliedExtrinsic {
+	let mut actual_weight = None;
+	let events = System::read_events_no_consensus()
+		.skip(event_count as usize)
+		.map(|record| {
+			if let RuntimeEvent::System(
+				frame_system::Event::ExtrinsicSuccess { dispatch_info } |
+				frame_system::Event::ExtrinsicFailed { dispatch_info, .. },
+			) = &record.event
+			{
+				actual_weight = Some(dispatch_info.weight);
+			}
+			record.event.encode()
+		})
+		.collect();
+
+	AppliedExtrinsic { events, actual_weight }
+}
+
+/// Lists the amounts that leave the signer's account when `encoded_call` is dispatched: the
+/// transaction fee, the protocol fees and the transfers or reserves made by the call itself.
+pub fn cost_estimate(encoded_call: Vec<u8>) -> Option<CostEstimate<AccountId, Balance>> {
+	use ternoa_transmission_protocols::types::TransmissionProtocol;
+
+	let call =
+		RuntimeCall::decode_with_depth_limit(sp_api::MAX_EXTRINSIC_DEPTH, &mut &*encoded_call)
+			.ok()?;
+	let charge = |destination, reason, amount| Charge { destination, reason, amount };
+
+	let mut charges = vec![charge(
+		ChargeDestination::TransactionFees,
+		ChargeReason::TransactionFee,
+		estimate_call_fee(call.clone()),
+	)];
+	match call {
+		RuntimeCall::NFT(ternoa_nft::Call::create_nft { .. }) => {
+			charges.push(charge(
+				ChargeDestination::Treasury,
+				ChargeReason::NftMintFee,
+				NFT::nft_mint_fee(),
+			));
+		},
+		RuntimeCall::NFT(ternoa_nft::Call::create_secret_nft { .. }) => {
+			charges.push(charge(
+				ChargeDestination::Treasury,
+				ChargeReason::NftMintFee,
+				NFT::nft_mint_fee(),
+			));
+			charges.push(charge(
+				ChargeDestination::Treasury,
+				ChargeReason::SecretNftMintFee,
+				NFT::secret_nft_mint_fee(),
+			));
+		},
+		RuntimeCall::NFT(ternoa_nft::Call::add_secret { .. }) => {
+			charges.push(charge(
+				ChargeDestination::Treasury,
+				ChargeReason::SecretNftMintFee,
+				NFT::secret_nft_mint_fee(),
+			));
+		},
+		RuntimeCall::NFT(ternoa_nft::Call::create_capsule { .. }) => {
+			charges.push(charge(
+				ChargeDestination::Treasury,
+				ChargeReason::NftMintFee,
+				NFT::nft_mint_fee(),
+			));
+			charges.push(charge(
+				ChargeDestination::Treasury,
+				ChargeReason::CapsuleMintFee,
+				NFT::capsule_mint_fee(),
+			));
+		},
+		RuntimeCall::NFT(ternoa_nft::Call::convert_to_capsule { .. }) => {
+			charges.push(charge(
+				ChargeDestination::Treasury,
+				ChargeReason::CapsuleMintFee,
+				NFT::capsule_mint_fee(),
+			));
+		},
+		RuntimeCall::Marketplace(ternoa_marketplace::Call::create_marketplace { .. }) => {
+			charges.push(charge(
+				ChargeDestination::Treasury,
+				ChargeReason::MarketplaceMintFee,
+				Marketplace::marketplace_mint_fee(),
+			));
+		},
+		RuntimeCall::Marketplace(ternoa_marketplace::Call::buy_nft { nft_id, .. }) => {
+			if let Some(quote) = buy_quote(nft_id) {
+				charges.push(charge(
+					ChargeDestination::Account(quote.seller),
+					ChargeReason::SellerProceeds,
+					quote.seller_proceeds,
+				));
+				charges.push(charge(
+					ChargeDestination::Account(quote.marketplace_owner),
+					ChargeReason::MarketplaceCommission,
+					quote.marketplace_commission,
+				));
+				charges.push(charge(
+					ChargeDestination::Account(quote.creator),
+					ChargeReason::Royalty,
+					quote.royalty,
+				));
+			}
+		},
+		RuntimeCall::Auction(ternoa_auction::Call::add_bid { amount, .. }) => {
+			charges.push(charge(ChargeDestination::PalletAccount, ChargeReason::Bid, amount));
+		},
+		RuntimeCall::Bridge(ternoa_bridge::Call::deposit { amount, .. }) => {
+			charges.push(charge(
+				ChargeDestination::Treasury,
+				ChargeReason::BridgeFee,
+				Bridge::bridge_fee(),
+			));
+			charges.push(charge(
+				ChargeDestination::PalletAccount,
+				ChargeReason::BridgedAmount,
+				amount,
+			));
+		},
+		RuntimeCall::TransmissionProtocols(
+			ternoa_transmission_protocols::Call::set_transmission_protocol { data, .. },
+		) => {
+			let (reason, fee) = match data {
+				TransmissionProtocol::AtBlock(..) =>
+					(ChargeReason::AtBlockFee, TransmissionProtocols::at_block_fee()),
+				TransmissionProtocol::AtBlockWithReset(..) => (
+					ChargeReason::AtBlockWithResetFee,
+					TransmissionProtocols::at_block_with_reset_fee(),
+				),
+				TransmissionProtocol::OnConsent { .. } =>
+					(ChargeReason::OnConsentFee, TransmissionProtocols::on_consent_fee()),
+				TransmissionProtocol::OnConsentAtBlock { .. } => (
+					ChargeReason::OnConsentAtBlockFee,
+					TransmissionProtocols::on_consent_at_block_fee(),
+				),
+			};
+			charges.push(charge(ChargeDestination::Treasury, reason, fee));
+		},
+		RuntimeCall::TEE(ternoa_tee::Call::register_enclave { .. }) => {
+			charges.push(charge(
+				ChargeDestination::Reserved,
+				ChargeReason::TeeStake,
+				TEE::staking_amount(),
+			));
+		},
+		_ => {},
+	}
+	charges.retain(|charge| charge.amount > 0);
+	let total = charges
+		.iter()
+		.fold(0, |total: Balance, charge| total.saturating_add(charge.amount));
+
+	Some(CostEstimate { charges, total })
+}
+
+/// Accepts the statements signed by a registered TEE enclave or its operator, or by the owner of
+/// the NFT that the first topic of the statement refers to.
+pub fn validate_statement(statement: Statement) -> Result<ValidStatement, InvalidStatement> {
+	let account: AccountId = match statement.verify_signature() {
+		SignatureVerificationResult::Valid(account) => account.into(),
+		SignatureVerificationResult::Invalid => return Err(InvalidStatement::BadProof),
+		SignatureVerificationResult::NoSignature => return Err(InvalidStatement::NoProof),
+	};
+
+	let is_enclave = ternoa_tee::EnclaveData::<Runtime>::contains_key(&account) ||
+		ternoa_tee::EnclaveAccountOperator::<Runtime>::contains_key(&account);
+	let is_nft_owner = || {
+		statement
+			.topic(0)
+			.and_then(|topic| nft_from_topic(&topic))
+			.and_then(NFT::nfts)
+			.map_or(false, |nft| nft.owner == account)
+	};
+
+	let (max_count, max_size) = if is_enclave {
+		ENCLAVE_STATEMENTS
+	} else if is_nft_owner() {
+		NFT_OWNER_STATEMENTS
+	} else {
+		return Err(InvalidStatement::BadProof)
+	};
+
+	Ok(ValidStatement { max_count, max_size })
+}
@@ -0,0 +1,176 @@
+// Copyright 2022 Capsule Corp (France) SAS.
+// This file is part of Ternoa.
+
+// Ternoa is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Ternoa is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Ternoa.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Invariants of the Ternoa pallets, checked by `try-runtime` on top of the pallets' own
+//! `try_state` hooks.
+
+use crate::{
+	pallets::{AuctionsPalletId, RentPalletId},
+	Balances, Runtime,
+};
+use frame_support::{ensure, traits::Currency};
+use frame_try_runtime::TryStateSelect;
+use sp_runtime::traits::AccountIdConversion;
+use ternoa_core_primitives::{AccountId, Balance};
+
+/// The pallets whose invariants are checked here, by their name in `construct_runtime!`.
+const TERNOA_PALLETS: [&[u8]; 5] = [b"NFT", b"Marketplace", b"Auction", b"Rent", b"TEE"];
+
+/// Returns whether the Ternoa invariants are selected: for every pallet and in round robin, or
+/// when at least one of the Ternoa pallets is named.
+pub fn is_selected(select: &TryStateSelect) -> bool {
+	match select {
+		TryStateSelect::None => false,
+		TryStateSelect::All | TryStateSelect::RoundRobin(_) => true,
+		TryStateSelect::Only(names) =>
+			names.iter().any(|name| TERNOA_PALLETS.contains(&name.as_slice())),
+	}
+}
+
+/// Checks all the Ternoa invariants, stopping at the first broken one.
+pub fn do_try_state() -> Result<(), &'static str> {
+	nft_collections()?;
+	marketplace_listings()?;
+	auctions()?;
+	rent_contracts()?;
+	tee_staking()?;
+
+	Ok(())
+}
+
+/// Every NFT of a collection is listed in it, and every listed NFT points back at the collection.
+fn nft_collections() -> Result<(), &'static str> {
+	for (nft_id, nft) in ternoa_nft::Nfts::<Runtime>::iter() {
+		if let Some(collection_id) = nft.collection_id {
+			let collection = ternoa_nft::Collections::<Runtime>::get(collection_id)
+				.ok_or("NFT belongs to a collection that does not exist")?;
+			ensure!(collection.nfts.contains(&nft_id), "NFT is missing from its collection");
+		}
+	}
+
+	for (collection_id, collection) in ternoa_nft::Collections::<Runtime>::iter() {
+		if let Some(limit) = collection.limit {
+			ensure!(collection.nfts.len() as u32 <= limit, "Collection exceeds its limit");
+		}
+		for nft_id in collection.nfts.iter() {
+			let nft = ternoa_nft::Nfts::<Runtime>::get(nft_id)
+				.ok_or("Collection holds an NFT that does not exist")?;
+			ensure!(
+				nft.collection_id == Some(collection_id),
+				"Collection holds an NFT of another collection"
+			);
+		}
+	}
+
+	Ok(())
+}
+
+/// Every sale is for an existing NFT, listed and owned by the seller, on an existing marketplace.
+fn marketplace_listings() -> Result<(), &'static str> {
+	for (nft_id, sale) in ternoa_marketplace::ListedNfts::<Runtime>::iter() {
+		let nft = ternoa_nft::Nfts::<Runtime>::get(nft_id).ok_or("Listed NFT does not exist")?;
+		ensure!(nft.state.listed_for_sale, "Listed NFT is not marked as listed");
+		ensure!(nft.owner == sale.account_id, "Listed NFT is not owned by the seller");
+		ensure!(
+			ternoa_marketplace::Marketplaces::<Runtime>::contains_key(sale.marketplace_id),
+			"NFT is listed on a marketplace that does not exist"
+		);
+	}
+
+	Ok(())
+}
+
+/// Every auctioned NFT exists and is owned by the auction creator, and the auction pallet account
+/// holds the bids and the unclaimed funds.
+fn auctions() -> Result<(), &'static str> {
+	let mut locked: Balance = 0;
+	for (nft_id, auction) in ternoa_auction::Auctions::<Runtime>::iter() {
+		let nft = ternoa_nft::Nfts::<Runtime>::get(nft_id).ok_or("Auctioned NFT does not exist")?;
+		ensure!(nft.state.listed_for_sale, "Auctioned NFT is not marked as listed");
+		ensure!(nft.owner == auction.creator, "Auctioned NFT is not owned by the creator");
+
+		locked = auction
+			.bidders
+			.list
+			.iter()
+			.fold(locked, |locked, (_, amount)| locked.saturating_add(*amount));
+	}
+	locked = ternoa_auction::Claims::<Runtime>::iter_values()
+		.fold(locked, |locked, amount| locked.saturating_add(amount));
+
+	let account: AccountId = AuctionsPalletId::get().into_account_truncating();
+	ensure!(
+		Balances::free_balance(&account) >= locked,
+		"Auction pallet account does not cover the bids and claims"
+	);
+
+	Ok(())
+}
+
+/// Every rented NFT exists and is owned by the renter, and the rent pallet account holds the
+/// cancellation fees deposited in tokens.
+fn rent_contracts() -> Result<(), &'static str> {
+	use ternoa_rent::types::CancellationFee;
+
+	let tokens = |fee: &CancellationFee<Balance>| match fee {
+		CancellationFee::FixedTokens(amount) | CancellationFee::FlexibleTokens(amount) => *amount,
+		_ => 0,
+	};
+
+	let mut locked: Balance = 0;
+	for (nft_id, contract) in ternoa_rent::Contracts::<Runtime>::iter() {
+		let nft = ternoa_nft::Nfts::<Runtime>::get(nft_id).ok_or("Rented NFT does not exist")?;
+		ensure!(nft.owner == contract.renter, "Rented NFT is not owned by the renter");
+
+		locked = locked.saturating_add(tokens(&contract.renter_cancellation_fee));
+		if contract.rentee.is_some() {
+			locked = locked.saturating_add(tokens(&contract.rentee_cancellation_fee));
+		}
+	}
+
+	let account: AccountId = RentPalletId::get().into_account_truncating();
+	ensure!(
+		Balances::free_balance(&account) >= locked,
+		"Rent pallet account does not cover the cancellation fees"
+	);
+
+	Ok(())
+}
+
+/// Every enclave operator has a staking ledger, and every ledger is backed by reserved funds.
+fn tee_staking() -> Result<(), &'static str> {
+	for operator in ternoa_tee::EnclaveData::<Runtime>::iter_keys() {
+		ensure!(
+			ternoa_tee::StakingLedger::<Runtime>::contains_key(&operator),
+			"Enclave operator has no staking ledger"
+		);
+	}
+
+	let mut total_staked: Balance = 0;
+	for (operator, ledger) in ternoa_tee::StakingLedger::<Runtime>::iter() {
+		ensure!(
+			Balances::reserved_balance(&operator) >= ledger.staked_amount,
+			"TEE staked amount is not reserved"
+		);
+		total_staked = total_staked.saturating_add(ledger.staked_amount);
+	}
+	ensure!(
+		total_staked <= Balances::total_issuance(),
+		"TEE staking total exceeds the total issuance"
+	);
+
+	Ok(())
+}